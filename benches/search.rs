@@ -0,0 +1,96 @@
+//! Search benchmarks over real crate documentation.
+//!
+//! By default the fixtures are fetched from docs.rs. To run offline, point
+//! `DOCSRS_BENCH_FIXTURES` at a directory of rustdoc JSON files instead:
+//!
+//! ```sh
+//! DOCSRS_BENCH_FIXTURES=/path/to/json cargo bench --features fetch
+//! ```
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use docsrs::{Doc, Indexed};
+use std::{hint::black_box, path::Path};
+
+const FIXTURES_ENV: &str = "DOCSRS_BENCH_FIXTURES";
+
+/// Crates fetched from docs.rs when no local fixtures are given, pinned so results stay comparable
+const CRATES: &[(&str, &str)] = &[
+    ("serde", "1.0.219"),
+    ("regex", "1.11.1"),
+    ("clap_builder", "4.5.40"),
+    ("tokio", "1.45.1"),
+];
+
+/// Simulates typing a query one character at a time
+const QUERIES: &[&str] = &[
+    "s",
+    "sp",
+    "spa",
+    "spaw",
+    "spawn",
+    "new",
+    "iter::map",
+    "Error",
+];
+
+fn load_fixtures() -> Vec<(String, Doc<Indexed>)> {
+    if let Ok(dir) = std::env::var(FIXTURES_ENV) {
+        let mut paths = std::fs::read_dir(&dir)
+            .unwrap_or_else(|e| panic!("failed to read `{FIXTURES_ENV}`={dir}: {e}"))
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        return paths
+            .iter()
+            .map(|path| (fixture_name(path), load_json(path)))
+            .collect();
+    }
+
+    CRATES
+        .iter()
+        .map(|(name, version)| {
+            let doc = Doc::from_docs(name, version)
+                .and_then(|doc| doc.fetch())
+                .and_then(|doc| doc.decompress())
+                .and_then(|doc| doc.parse())
+                .unwrap_or_else(|e| panic!("failed to load {name} {version}: {e}"))
+                .build_search_index();
+            (format!("{name}-{version}"), doc)
+        })
+        .collect()
+}
+
+fn fixture_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn load_json(path: &Path) -> Doc<Indexed> {
+    Doc::from_json(path)
+        .and_then(|doc| doc.parse())
+        .unwrap_or_else(|e| panic!("failed to load {}: {e}", path.display()))
+        .build_search_index()
+}
+
+fn bench_search(c: &mut Criterion) {
+    let fixtures = load_fixtures();
+
+    for (name, doc) in &fixtures {
+        let mut group = c.benchmark_group(format!("search/{name}"));
+        for query in QUERIES {
+            group.bench_with_input(BenchmarkId::new("top10", query), query, |b, query| {
+                b.iter(|| doc.search(black_box(query), Some(10)))
+            });
+            group.bench_with_input(BenchmarkId::new("all", query), query, |b, query| {
+                b.iter(|| doc.search(black_box(query), None))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_search);
+criterion_main!(benches);
@@ -0,0 +1,486 @@
+//! A language server answering symbol searches and hovers from the docs of a project's dependencies.
+//!
+//! On startup the direct dependencies of the workspace are read from its `Cargo.lock` and
+//! their docs are fetched from docs.rs. Clients can pass `{"docs": ["path/to/doc.json"]}` as
+//! initialization options to serve local rustdoc JSON files instead.
+//!
+//! Besides `workspace/symbol` and `textDocument/hover` the server answers the custom
+//! `docsrs/search` request, see [`Search`].
+
+use docsrs::{Doc, Indexed, Item, SearchHit};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as NotificationTrait,
+};
+use lsp_types::request::{HoverRequest, Request as RequestTrait, WorkspaceSymbolRequest};
+use lsp_types::{
+    Hover, HoverContents, HoverParams, HoverProviderCapability, InitializeParams, Location,
+    MarkupContent, MarkupKind, OneOf, Position, Range, ServerCapabilities, SymbolInformation,
+    SymbolKind, SymbolTag, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+    WorkspaceSymbolParams, WorkspaceSymbolResponse,
+};
+use rustdoc_types::ItemKind;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The maximum number of results of a `workspace/symbol` request
+const MAX_SYMBOLS: usize = 100;
+
+/// The custom `docsrs/search` request, a fuzzy search over the docs returning the items
+/// with their signatures and docs.
+enum Search {}
+
+impl RequestTrait for Search {
+    type Params = SearchParams;
+    type Result = Vec<SearchResult>;
+    const METHOD: &'static str = "docsrs/search";
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct SearchParams {
+    /// The search query, e.g. `Vec::push`
+    query: String,
+    /// The maximum number of results [default: 10]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct SearchResult {
+    /// The matched path, e.g. `tokio::spawn`
+    path: String,
+    name: String,
+    /// The kind in snake case, e.g. `struct`
+    kind: Option<String>,
+    signature: Option<String>,
+    docs: Option<String>,
+    /// The page of the item on docs.rs
+    url: Option<String>,
+    score: i64,
+}
+
+/// The options clients can pass in `initializationOptions`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Options {
+    /// Paths of rustdoc JSON files to serve instead of the docs of the dependencies
+    docs: Vec<PathBuf>,
+}
+
+fn main() -> Result<(), BoxError> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        workspace_symbol_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    };
+    let params = connection.initialize(serde_json::to_value(capabilities)?)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+
+    let server = Server {
+        docs: load_docs(&params),
+        documents: HashMap::new(),
+    };
+    // The writer thread finishes once the connection is dropped
+    server.run(connection)?;
+
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Loads the docs given in the initialization options, or else the docs of the
+/// dependencies of the workspace. Docs that fail to load are reported on stderr.
+fn load_docs(params: &InitializeParams) -> Vec<Doc<Indexed>> {
+    let options: Options = params
+        .initialization_options
+        .clone()
+        .and_then(|options| serde_json::from_value(options).ok())
+        .unwrap_or_default();
+
+    if !options.docs.is_empty() {
+        return options
+            .docs
+            .iter()
+            .filter_map(|path| {
+                let doc = Doc::from_json(path).and_then(|doc| doc.parse());
+                doc.inspect_err(|e| eprintln!("failed to load {}: {e}", path.display()))
+                    .ok()
+            })
+            .map(|doc| doc.build_search_index())
+            .collect();
+    }
+
+    let Some(root) = workspace_root(params) else {
+        return Vec::new();
+    };
+    let lockfile = match std::fs::read_to_string(root.join("Cargo.lock")) {
+        Ok(lockfile) => lockfile,
+        Err(e) => {
+            eprintln!("failed to read Cargo.lock in {}: {e}", root.display());
+            return Vec::new();
+        }
+    };
+
+    direct_dependencies(&lockfile)
+        .into_iter()
+        .filter_map(|(name, version)| {
+            let doc = Doc::from_docs(&name, &version)
+                .and_then(|doc| doc.fetch())
+                .and_then(|doc| doc.decompress())
+                .and_then(|doc| doc.parse());
+            doc.inspect_err(|e| eprintln!("failed to load the docs of {name} {version}: {e}"))
+                .ok()
+        })
+        .map(|doc| doc.build_search_index())
+        .collect()
+}
+
+fn workspace_root(params: &InitializeParams) -> Option<PathBuf> {
+    let folder = params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|folder| &folder.uri);
+    #[allow(deprecated)]
+    let uri = folder.or(params.root_uri.as_ref())?;
+    uri.to_file_path().ok()
+}
+
+/// The names and versions of the dependencies of the workspace members in a `Cargo.lock`
+///
+/// Workspace members are the packages without a `source`, dependencies between members
+/// are left out.
+fn direct_dependencies(lockfile: &str) -> Vec<(String, String)> {
+    struct Package<'a> {
+        name: &'a str,
+        version: &'a str,
+        is_member: bool,
+        dependencies: Vec<&'a str>,
+    }
+
+    let packages: Vec<Package> = lockfile
+        .split("[[package]]")
+        .skip(1)
+        .filter_map(|block| {
+            let value = |key: &str| {
+                block.lines().find_map(|line| {
+                    let value = line.strip_prefix(key)?.trim_start().strip_prefix('=')?;
+                    Some(value.trim().trim_matches('"'))
+                })
+            };
+            let dependencies = block
+                .split_once("dependencies = [")
+                .and_then(|(_, rest)| rest.split_once(']'))
+                .map(|(list, _)| {
+                    list.split(',')
+                        .map(|dependency| dependency.trim().trim_matches('"'))
+                        .filter(|dependency| !dependency.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            Some(Package {
+                name: value("name")?,
+                version: value("version")?,
+                is_member: value("source").is_none(),
+                dependencies,
+            })
+        })
+        .collect();
+
+    let mut dependencies: Vec<(String, String)> = packages
+        .iter()
+        .filter(|package| package.is_member)
+        .flat_map(|package| &package.dependencies)
+        .filter_map(|dependency| {
+            // Either `name` or `name version`, the latter if several versions are locked
+            let mut parts = dependency.split_whitespace();
+            let name = parts.next()?;
+            let version = parts.next();
+            let package = packages.iter().find(|package| {
+                package.name == name && version.is_none_or(|version| package.version == version)
+            })?;
+            (!package.is_member).then(|| (package.name.to_string(), package.version.to_string()))
+        })
+        .collect();
+    dependencies.sort();
+    dependencies.dedup();
+    dependencies
+}
+
+struct Server {
+    docs: Vec<Doc<Indexed>>,
+    /// The contents of the open text documents
+    documents: HashMap<Url, String>,
+}
+
+impl Server {
+    fn run(mut self, connection: Connection) -> Result<(), BoxError> {
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification),
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            WorkspaceSymbolRequest::METHOD => {
+                respond::<WorkspaceSymbolRequest>(request, |params| self.workspace_symbol(params))
+            }
+            HoverRequest::METHOD => respond::<HoverRequest>(request, |params| self.hover(params)),
+            Search::METHOD => respond::<Search>(request, |params| self.search(params)),
+            method => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unknown method {method}"),
+            ),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                if let Ok(params) = notification
+                    .extract::<lsp_types::DidOpenTextDocumentParams>(DidOpenTextDocument::METHOD)
+                {
+                    let document = params.text_document;
+                    self.documents.insert(document.uri, document.text);
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                if let Ok(params) = notification.extract::<lsp_types::DidChangeTextDocumentParams>(
+                    DidChangeTextDocument::METHOD,
+                ) && let Some(change) = params.content_changes.into_iter().last()
+                {
+                    // Full sync, every change is the whole document
+                    self.documents.insert(params.text_document.uri, change.text);
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                if let Ok(params) = notification
+                    .extract::<lsp_types::DidCloseTextDocumentParams>(DidCloseTextDocument::METHOD)
+                {
+                    self.documents.remove(&params.text_document.uri);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The best `n` hits of all docs
+    fn search_all(&self, query: &str, n: usize) -> Vec<SearchHit<'_>> {
+        let mut hits: Vec<SearchHit> = self
+            .docs
+            .iter()
+            .filter_map(|doc| doc.search_detailed(query, n))
+            .flatten()
+            .collect();
+        hits.sort_by_key(|hit| std::cmp::Reverse(hit.score));
+        hits.truncate(n);
+        hits
+    }
+
+    /// The docs.rs page of an item, for fields, variants and associated items the
+    /// page of their parent
+    fn symbol_url(&self, item: &Item) -> Option<Url> {
+        if let Some(url) = item.url().ok()? {
+            return Some(url);
+        }
+        let (_, parent) = item.path.split_last()?;
+        let parent = self
+            .docs
+            .iter()
+            .find_map(|doc| doc.get_by_path(&parent.join("::")))?;
+        self.symbol_url(parent)
+    }
+
+    fn workspace_symbol(&self, params: WorkspaceSymbolParams) -> Option<WorkspaceSymbolResponse> {
+        let symbols = self
+            .search_all(&params.query, MAX_SYMBOLS)
+            .into_iter()
+            .filter_map(|hit| {
+                let item = hit.item;
+                let uri = self.symbol_url(item)?;
+                let (_, parent) = item.path.split_last()?;
+                #[allow(deprecated)]
+                Some(SymbolInformation {
+                    name: item.name.clone(),
+                    kind: symbol_kind(item.kind),
+                    tags: item
+                        .deprecation
+                        .as_ref()
+                        .map(|_| vec![SymbolTag::DEPRECATED]),
+                    deprecated: None,
+                    location: Location::new(uri, Range::default()),
+                    container_name: Some(parent.join("::")),
+                })
+            })
+            .collect();
+        Some(WorkspaceSymbolResponse::Flat(symbols))
+    }
+
+    fn search(&self, params: SearchParams) -> Vec<SearchResult> {
+        self.search_all(&params.query, params.limit.unwrap_or(10))
+            .into_iter()
+            .map(|hit| SearchResult {
+                path: hit.matched_key.to_string(),
+                name: hit.item.name.clone(),
+                kind: hit.item.kind.and_then(|kind| {
+                    serde_json::to_value(kind)
+                        .ok()?
+                        .as_str()
+                        .map(str::to_string)
+                }),
+                signature: hit.item.signature(),
+                docs: hit.item.docs.clone(),
+                url: hit.item.url().ok().flatten().map(String::from),
+                score: hit.score,
+            })
+            .collect()
+    }
+
+    /// The signature and docs of the item named by the path under the cursor
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let text = self.documents.get(&position.text_document.uri)?;
+        let (path, range) = path_at(text, position.position)?;
+
+        // A path from the crate root must resolve exactly, paths relative to an import
+        // like `Mutex::new` or `Wrapper` match the end of an item's path, the shortest
+        // first. A fuzzy match for an unknown identifier would be misleading.
+        let item = match self.docs.iter().find_map(|doc| doc.get_by_path(path)) {
+            Some(item) => item,
+            None => {
+                let segments: Vec<&str> = path.split("::").collect();
+                self.docs
+                    .iter()
+                    .flat_map(|doc| doc.items())
+                    .filter(|item| {
+                        std::iter::once(&item.path)
+                            .chain(&item.aliases)
+                            .any(|item_path| ends_with(item_path, &segments))
+                    })
+                    .min_by_key(|item| item.path.len())?
+            }
+        };
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: hover_text(item),
+            }),
+            range: Some(range),
+        })
+    }
+}
+
+/// Answers a request with the result of `handler`, or with an error if the params are invalid
+fn respond<R: RequestTrait>(
+    request: Request,
+    handler: impl FnOnce(R::Params) -> R::Result,
+) -> Response {
+    match serde_json::from_value(request.params) {
+        Ok(params) => Response::new_ok(request.id, handler(params)),
+        Err(e) => Response::new_err(request.id, ErrorCode::InvalidParams as i32, e.to_string()),
+    }
+}
+
+fn hover_text(item: &Item) -> String {
+    let path = item
+        .canonical_path
+        .as_ref()
+        .unwrap_or(&item.path)
+        .join("::");
+    let mut text = format!("```rust\n{path}\n```\n");
+    if let Some(signature) = item.signature() {
+        text.push_str(&format!("\n```rust\n{signature}\n```\n"));
+    }
+    if let Some(docs) = &item.docs {
+        text.push_str(&format!("\n---\n\n{docs}\n"));
+    }
+    if let Ok(Some(url)) = item.url() {
+        text.push_str(&format!("\n[docs.rs]({url})\n"));
+    }
+    text
+}
+
+/// Whether `path` ends with `segments`, e.g. `["tokio", "sync", "Mutex", "new"]`
+/// with `["Mutex", "new"]`
+fn ends_with(path: &[String], segments: &[&str]) -> bool {
+    path.len() >= segments.len()
+        && path[path.len() - segments.len()..]
+            .iter()
+            .zip(segments)
+            .all(|(segment, other)| segment == other)
+}
+
+/// The path up to the end of the identifier under the cursor, and its range
+///
+/// With the cursor on `sync` in `tokio::sync::Mutex` this is `tokio::sync`.
+fn path_at(text: &str, position: Position) -> Option<(&str, Range)> {
+    let line = text.lines().nth(position.line as usize)?;
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+    let is_path_char = |c: char| is_ident_char(c) || c == ':';
+
+    // Positions count UTF-16 code units
+    let mut units = 0;
+    let cursor = line
+        .char_indices()
+        .find(|(_, c)| {
+            units += c.len_utf16() as u32;
+            units > position.character
+        })
+        .map_or(line.len(), |(i, _)| i);
+
+    let start = line[..cursor]
+        .rfind(|c| !is_path_char(c))
+        .map_or(0, |i| i + 1);
+    let end = line[cursor..]
+        .find(|c| !is_ident_char(c))
+        .map_or(line.len(), |i| cursor + i);
+    let path = line[start..end].trim_matches(':');
+    if path.is_empty() {
+        return None;
+    }
+
+    let start = start + line[start..].find(path)?;
+    let column = |byte: usize| line[..byte].encode_utf16().count() as u32;
+    let range = Range::new(
+        Position::new(position.line, column(start)),
+        Position::new(position.line, column(start + path.len())),
+    );
+    Some((path, range))
+}
+
+fn symbol_kind(kind: Option<ItemKind>) -> SymbolKind {
+    match kind {
+        Some(ItemKind::Module) => SymbolKind::MODULE,
+        Some(ItemKind::Struct | ItemKind::Union) => SymbolKind::STRUCT,
+        Some(ItemKind::Enum) => SymbolKind::ENUM,
+        Some(ItemKind::Variant) => SymbolKind::ENUM_MEMBER,
+        Some(ItemKind::StructField) => SymbolKind::FIELD,
+        Some(ItemKind::Trait | ItemKind::TraitAlias) => SymbolKind::INTERFACE,
+        Some(
+            ItemKind::Function | ItemKind::Macro | ItemKind::ProcAttribute | ItemKind::ProcDerive,
+        ) => SymbolKind::FUNCTION,
+        Some(ItemKind::TypeAlias | ItemKind::AssocType) => SymbolKind::TYPE_PARAMETER,
+        Some(ItemKind::Constant | ItemKind::AssocConst) => SymbolKind::CONSTANT,
+        Some(ItemKind::Static) => SymbolKind::VARIABLE,
+        _ => SymbolKind::OBJECT,
+    }
+}
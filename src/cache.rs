@@ -0,0 +1,194 @@
+//! Loaded crates shared between the commands of one process, like the daemon
+
+use docsrs::{Doc, Error, Indexed, Parsed, RawJson};
+use std::cell::OnceCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// How long the `latest` version of a crate is kept before it is loaded again, to
+/// pick up new releases
+const LATEST_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// The docs of a crate, indexed on first use
+pub struct Loaded {
+    pub parsed: Doc<Parsed>,
+    indexed: OnceCell<Doc<Indexed>>,
+    /// The size of the rustdoc JSON, the weight of the crate in the budget
+    ///
+    /// The JSON itself is dropped after parsing, the parsed crate takes a few times
+    /// as much memory.
+    pub size: usize,
+    loaded_at: Instant,
+}
+
+impl Loaded {
+    pub fn indexed(&self) -> &Doc<Indexed> {
+        self.indexed
+            .get_or_init(|| self.parsed.build_search_index())
+    }
+}
+
+/// Loads the rustdoc JSON of a crate by name and version
+pub type Loader = Box<dyn Fn(&str, &str) -> Result<Doc<RawJson>, Error>>;
+
+/// Crates by name and version, the least recently used are evicted to stay within a budget
+///
+/// The budget only counts the rustdoc JSON of the crates, not the memory of the
+/// parsed crates and their search indexes.
+pub struct Cache {
+    /// The most recently used crate last
+    entries: Vec<((String, String), Rc<Loaded>)>,
+    /// The budget in bytes of rustdoc JSON
+    budget: usize,
+    /// How long `latest` versions are kept, see [`LATEST_TTL`]
+    latest_ttl: Duration,
+    load: Loader,
+}
+
+impl Cache {
+    /// A cache fetching crates from docs.rs
+    pub fn new(budget: usize) -> Self {
+        Self::with_loader(budget, Box::new(fetch))
+    }
+
+    /// A cache reading the crates of `files` from their rustdoc JSON, whatever the
+    /// version asked for, and fetching the others from docs.rs
+    ///
+    /// The files are named after their crate like rustdoc names them, e.g.
+    /// `target/doc/serde_json.json` for `serde_json` or `serde-json`.
+    pub fn with_files(budget: usize, files: Vec<PathBuf>) -> Self {
+        Self::with_loader(
+            budget,
+            Box::new(move |crate_name, version| {
+                let file_name = crate_name.replace('-', "_");
+                match files
+                    .iter()
+                    .find(|file| file.file_stem().is_some_and(|stem| *stem == *file_name))
+                {
+                    Some(file) => Doc::from_json(file),
+                    None => fetch(crate_name, version),
+                }
+            }),
+        )
+    }
+
+    /// A cache loading crates with `load`, e.g. from local files
+    pub fn with_loader(budget: usize, load: Loader) -> Self {
+        Self {
+            entries: Vec::new(),
+            budget,
+            latest_ttl: LATEST_TTL,
+            load,
+        }
+    }
+
+    /// A cache that keeps every crate
+    pub fn unbounded() -> Self {
+        Self::new(usize::MAX)
+    }
+
+    /// Returns a crate, loading it if it isn't loaded yet
+    ///
+    /// The crate just loaded is kept even if it alone exceeds the budget. The
+    /// `latest` version is loaded again once it is older than an hour.
+    pub fn get(&mut self, crate_name: &str, version: &str) -> Result<Rc<Loaded>, Error> {
+        let key = (crate_name.to_string(), version.to_string());
+        if let Some(i) = self.entries.iter().position(|(k, _)| *k == key) {
+            let entry = self.entries.remove(i);
+            if version != "latest" || entry.1.loaded_at.elapsed() < self.latest_ttl {
+                let loaded = entry.1.clone();
+                self.entries.push(entry);
+                return Ok(loaded);
+            }
+        }
+
+        let raw = (self.load)(crate_name, version)?;
+        let size = raw.size();
+        let loaded = Rc::new(Loaded {
+            parsed: raw.parse()?,
+            indexed: OnceCell::new(),
+            size,
+            loaded_at: Instant::now(),
+        });
+
+        while !self.entries.is_empty() && self.used().saturating_add(size) > self.budget {
+            self.entries.remove(0);
+        }
+        self.entries.push((key, loaded.clone()));
+        Ok(loaded)
+    }
+
+    /// The loaded crates as `(name, version, size)`, the most recently used last
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str, usize)> {
+        self.entries
+            .iter()
+            .map(|((name, version), loaded)| (name.as_str(), version.as_str(), loaded.size))
+    }
+
+    fn used(&self) -> usize {
+        self.entries.iter().map(|(_, loaded)| loaded.size).sum()
+    }
+}
+
+/// Fetches the rustdoc JSON of a crate from docs.rs
+fn fetch(crate_name: &str, version: &str) -> Result<Doc<RawJson>, Error> {
+    Doc::from_docs(crate_name, version)?.fetch()?.decompress()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = "tests/fixtures/fixture.json";
+
+    fn names(cache: &Cache) -> Vec<&str> {
+        cache.entries().map(|(name, _, _)| name).collect()
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let size = Doc::from_json(FIXTURE).unwrap().size();
+        let mut cache = Cache::with_loader(2 * size, Box::new(|_, _| Doc::from_json(FIXTURE)));
+
+        cache.get("a", "latest").unwrap();
+        cache.get("b", "latest").unwrap();
+        assert_eq!(names(&cache), ["a", "b"]);
+
+        // Using `a` again makes `b` the least recently used
+        let a = cache.get("a", "latest").unwrap();
+        assert_eq!(names(&cache), ["b", "a"]);
+        cache.get("c", "latest").unwrap();
+        assert_eq!(names(&cache), ["a", "c"]);
+        assert!(Rc::ptr_eq(&a, &cache.get("a", "latest").unwrap()));
+
+        // A crate over the budget on its own is still kept
+        let mut cache = Cache::with_loader(size / 2, Box::new(|_, _| Doc::from_json(FIXTURE)));
+        cache.get("a", "latest").unwrap();
+        cache.get("b", "latest").unwrap();
+        assert_eq!(names(&cache), ["b"]);
+    }
+
+    #[test]
+    fn versions_are_separate_entries() {
+        let mut cache = Cache::with_loader(usize::MAX, Box::new(|_, _| Doc::from_json(FIXTURE)));
+        let first = cache.get("a", "1.0.0").unwrap();
+        let second = cache.get("a", "2.0.0").unwrap();
+        assert!(!Rc::ptr_eq(&first, &second));
+        assert_eq!(cache.entries().count(), 2);
+    }
+
+    #[test]
+    fn reload_latest() {
+        let mut cache = Cache::with_loader(usize::MAX, Box::new(|_, _| Doc::from_json(FIXTURE)));
+        let pinned = cache.get("a", "1.0.0").unwrap();
+        let latest = cache.get("a", "latest").unwrap();
+        assert!(Rc::ptr_eq(&latest, &cache.get("a", "latest").unwrap()));
+
+        // Once outdated, `latest` is loaded again and replaces the old entry
+        cache.latest_ttl = Duration::ZERO;
+        assert!(!Rc::ptr_eq(&latest, &cache.get("a", "latest").unwrap()));
+        assert!(Rc::ptr_eq(&pinned, &cache.get("a", "1.0.0").unwrap()));
+        assert_eq!(cache.entries().count(), 2);
+    }
+}
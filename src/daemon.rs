@@ -0,0 +1,196 @@
+//! The daemon of `docsrs daemon` and the client side of the other commands
+//!
+//! The client sends its arguments and how to render for its terminal as one JSON
+//! line over a Unix socket: `{"args": [string], "color": bool, "width": number}`.
+//! The daemon runs the command with its loaded crates and replies with the output
+//! as one JSON line: `{"stdout": string, "stderr": string, "error": string or null}`.
+
+use crate::cache::Cache;
+use crate::{Args, Output, execute};
+use clap::Parser;
+use docsrs::Error;
+use serde_json::{Value, json};
+use std::fs::DirBuilder;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::os::unix::fs::{DirBuilderExt, FileTypeExt, MetadataExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long the daemon waits for a client to send its request or take the reply
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The socket the daemon listens on
+///
+/// `$DOCSRS_SOCKET` if set, else `docsrs.sock` in `$XDG_RUNTIME_DIR` or, without
+/// a runtime directory, in a `docsrs-<uid>` directory of the temp directory that
+/// only the current user can access. The directory is created by [`run`].
+pub fn socket_path() -> PathBuf {
+    if let Some(path) = std::env::var_os("DOCSRS_SOCKET") {
+        return path.into();
+    }
+    if let Some(dir) = std::env::var_os("XDG_RUNTIME_DIR") {
+        return PathBuf::from(dir).join("docsrs.sock");
+    }
+    private_dir().join("docsrs.sock")
+}
+
+/// The directory of the socket without a runtime directory
+fn private_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("docsrs-{}", current_uid()))
+}
+
+/// Creates the directory of [`private_dir`], checking that nobody else created it
+fn create_private_dir(dir: &Path) -> Result<(), Error> {
+    match DirBuilder::new().mode(0o700).create(dir) {
+        Err(e) if e.kind() != ErrorKind::AlreadyExists => return Err(e.into()),
+        _ => {}
+    }
+    // The directory may have been created by someone else before us
+    let metadata = std::fs::symlink_metadata(dir)?;
+    if !metadata.is_dir() || metadata.uid() != current_uid() || metadata.mode() & 0o077 != 0 {
+        let message = format!(
+            "{} isn't a private directory of the current user",
+            dir.display()
+        );
+        return Err(std::io::Error::other(message).into());
+    }
+    Ok(())
+}
+
+fn current_uid() -> u32 {
+    // SAFETY: getuid has no preconditions and always succeeds
+    unsafe { libc::getuid() }
+}
+
+/// Serves commands until killed, keeping the crates within `budget` bytes of rustdoc JSON
+pub fn run(socket: &Path, budget: usize) -> Result<(), Error> {
+    let dir = private_dir();
+    if socket.parent() == Some(dir.as_path()) {
+        create_private_dir(&dir)?;
+    }
+    if UnixStream::connect(socket).is_ok() {
+        let message = format!("a daemon is already listening on {}", socket.display());
+        return Err(std::io::Error::other(message).into());
+    }
+    // A socket left behind by a daemon that didn't shut down cleanly, anything
+    // else at the path is left alone
+    match std::fs::symlink_metadata(socket) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(socket)?,
+        Ok(_) => {
+            let message = format!("{} exists and isn't a socket", socket.display());
+            return Err(std::io::Error::other(message).into());
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    let listener = UnixListener::bind(socket)?;
+    eprintln!("listening on {}", socket.display());
+
+    let mut cache = Cache::new(budget);
+    for stream in listener.incoming() {
+        if let Err(e) = stream
+            .map_err(Error::from)
+            .and_then(|stream| serve(stream, &mut cache))
+        {
+            eprintln!("failed to serve a command: {e}");
+        }
+    }
+    Ok(())
+}
+
+/// Runs the command of one client
+fn serve(stream: UnixStream, cache: &mut Cache) -> Result<(), Error> {
+    // Clients are served one at a time, a client that doesn't send its request or
+    // read the reply mustn't hold up the others
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+
+    let mut request = String::new();
+    // A client checking whether a daemon is listening
+    if BufReader::new(&stream).read_line(&mut request)? == 0 {
+        return Ok(());
+    }
+    let request: Value = serde_json::from_str(&request)?;
+
+    let arguments: Vec<&str> = request["args"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    eprintln!("docsrs {}", arguments.join(" "));
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let result = Args::try_parse_from(std::iter::once("docsrs").chain(arguments))
+        .map_err(|e| e.to_string())
+        .and_then(|args| {
+            let mut output = Output {
+                format: args.format,
+                color: request["color"].as_bool().unwrap_or(false),
+                width: request["width"]
+                    .as_u64()
+                    .and_then(|width| usize::try_from(width).ok()),
+                out: &mut stdout,
+                err: &mut stderr,
+            };
+            execute(args, cache, &mut output).map_err(|e| format!("{e:?}"))
+        });
+
+    let reply = json!({
+        "stdout": String::from_utf8_lossy(&stdout),
+        "stderr": String::from_utf8_lossy(&stderr),
+        "error": result.err(),
+    });
+    writeln!(&stream, "{reply}")?;
+
+    let loaded: Vec<String> = cache
+        .entries()
+        .map(|(name, version, size)| format!("{name} {version} ({} MiB)", size / (1024 * 1024)))
+        .collect();
+    eprintln!("loaded: {}", loaded.join(", "));
+    Ok(())
+}
+
+/// Runs the command of this process in the daemon and prints its output
+///
+/// Returns `false` if no daemon is listening on `socket`, to run the command in
+/// this process instead. Errors of the command end the process like local ones.
+/// A socket of another user is an error, its daemon could reply anything.
+pub fn forward(socket: &Path, color: bool, width: Option<usize>) -> Result<bool, Error> {
+    match std::fs::metadata(socket) {
+        Ok(metadata) if metadata.uid() != current_uid() => {
+            let message = format!("{} belongs to another user", socket.display());
+            return Err(std::io::Error::other(message).into());
+        }
+        Ok(_) => {}
+        Err(_) => return Ok(false),
+    }
+    let Ok(stream) = UnixStream::connect(socket) else {
+        return Ok(false);
+    };
+
+    let arguments: Vec<String> = std::env::args_os()
+        .skip(1)
+        .map(|argument| argument.to_string_lossy().into_owned())
+        .collect();
+    let request = json!({ "args": arguments, "color": color, "width": width });
+    writeln!(&stream, "{request}")?;
+
+    let mut reply = String::new();
+    BufReader::new(&stream).read_line(&mut reply)?;
+    // The daemon went away without replying
+    let Ok(reply) = serde_json::from_str::<Value>(&reply) else {
+        return Ok(false);
+    };
+
+    print!("{}", reply["stdout"].as_str().unwrap_or_default());
+    eprint!("{}", reply["stderr"].as_str().unwrap_or_default());
+    if let Some(error) = reply["error"].as_str() {
+        eprintln!("Error: {error}");
+        std::process::exit(1);
+    }
+    Ok(true)
+}
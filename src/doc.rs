@@ -12,10 +12,27 @@ mod rawjson;
 pub use rawjson::RawJson;
 
 mod parsed;
-pub use parsed::{Item, Parsed};
+pub use parsed::{
+    Coverage, CoverageReport, DocIssue, DocLint, IndexOptions, Item, ModuleTree, Parsed,
+    PathPreference,
+};
+
+mod examples;
+pub use examples::Example;
+
+mod markdown;
+pub use markdown::{MarkdownLine, MarkdownSpan, TerminalOptions};
+
+mod signature;
 
 mod indexed;
-pub use indexed::Indexed;
+#[cfg(feature = "nucleo")]
+pub use indexed::NucleoRanker;
+pub use indexed::{
+    ApiChange, ApiDiff, Candidate, ChangeKind, ExportFormat, ImplKind, Implementation, Indexed,
+    KindWeights, LastSegmentBoost, Method, MethodOrigin, Penalize, PrefixBoost, Ranker, SearchHit,
+    Severity, SkimRanker,
+};
 
 /// A generic wrapper for documentation data in different processing states.
 ///
@@ -54,6 +71,7 @@ mod tests {
     use super::*;
     use crate::logging::init_logger;
 
+    #[cfg(feature = "fetch")]
     fn assert_path_superset(superset_path: &[String], subset_path: &[&str]) {
         let is_superset = subset_path
             .iter()
@@ -67,6 +85,955 @@ mod tests {
         );
     }
 
+    fn fixture() -> Doc<Indexed> {
+        Doc::from_json(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/fixture.json"
+        ))
+        .unwrap()
+        .parse()
+        .unwrap()
+        .build_search_index()
+    }
+
+    #[test]
+    fn search_fixture() {
+        init_logger();
+
+        let krate = fixture();
+
+        let hits = krate.search("fixture::config::Config", None).unwrap();
+        assert_eq!(hits.len(), 1, "exact match should be the only result");
+        assert_eq!(hits[0].name, "Config");
+
+        let hits = krate.search("timeout", 2).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().any(|item| item.name == "timeout"));
+
+        let all = krate.search("config", None).unwrap();
+        let top = krate.search("config", 3).unwrap();
+        assert_eq!(top, all[..3], "top-k selection must agree with a full sort");
+
+        assert!(krate.search("zzz::qqq", None).is_none());
+    }
+
+    #[test]
+    fn search_with_rankers() {
+        init_logger();
+
+        let krate = fixture();
+
+        let ranker = KindWeights::new(SkimRanker::default());
+        let hits = krate.search_with("config", None, &ranker).unwrap();
+        assert_eq!(
+            hits[0].kind,
+            Some(rustdoc_types::ItemKind::Struct),
+            "types should rank first, got {:?}",
+            hits.iter()
+                .map(|item| item.path.join("::"))
+                .collect::<Vec<_>>()
+        );
+
+        let ranker = Penalize::new(SkimRanker::default());
+        let hits = krate.search_with("_api", None, &ranker).unwrap();
+        let names: Vec<_> = hits.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names[..2], ["new_api", "old_api"]);
+
+        #[cfg(feature = "nucleo")]
+        {
+            let hits = krate
+                .search_with("config timeout", 1, &NucleoRanker::default())
+                .unwrap();
+            assert_eq!(hits[0].path.join("::"), "fixture::config::Config::timeout");
+        }
+    }
+
+    #[test]
+    fn search_detailed_fixture() {
+        init_logger();
+
+        let krate = fixture();
+
+        let hits = krate.search_detailed("cfgtimeout", 1).unwrap();
+        let hit = &hits[0];
+        assert_eq!(hit.matched_key, "fixture::config::Config::timeout");
+        assert_eq!(hit.item.name, "timeout");
+        assert!(hit.score > 0);
+
+        let matched: String = hit
+            .matched_key
+            .chars()
+            .enumerate()
+            .filter(|(i, _)| hit.match_indices.contains(i))
+            .map(|(_, c)| c.to_ascii_lowercase())
+            .collect();
+        assert_eq!(matched, "cfgtimeout");
+    }
+
+    #[test]
+    fn search_dedups_reexports() {
+        init_logger();
+
+        let krate = fixture();
+
+        let hits = krate.search("Config", None).unwrap();
+        let configs: Vec<_> = hits.iter().filter(|item| item.name == "Config").collect();
+        assert_eq!(
+            configs.len(),
+            1,
+            "re-exports should be merged into one result"
+        );
+
+        let config = configs[0];
+        assert_eq!(config.path, ["fixture", "config", "Config"]);
+        assert_eq!(config.aliases, [["fixture", "prelude", "Config"]]);
+
+        let hit = krate.search("fixture::prelude::Config", 1).unwrap();
+        assert_eq!(
+            hit[0], *config,
+            "an alias should resolve to the re-exported item"
+        );
+        assert_eq!(
+            config.path_by(PathPreference::Shortest),
+            ["fixture", "config", "Config"]
+        );
+    }
+
+    #[test]
+    fn expand_glob_reexports() {
+        init_logger();
+
+        let krate = fixture();
+
+        // `pub use crate::shapes::*` in the prelude
+        let shape = krate.search("fixture::prelude::Shape", 1).unwrap()[0];
+        assert_eq!(shape.path, ["fixture", "shapes", "Shape"]);
+        assert!(shape.aliases.contains(&vec![
+            "fixture".to_string(),
+            "prelude".to_string(),
+            "Shape".to_string()
+        ]));
+        let area = krate.search("fixture::prelude::Area", 1).unwrap()[0];
+        assert_eq!(area.path, ["fixture", "shapes", "Area"]);
+
+        // `pub use inner::*` of a private module
+        let reexported = krate.search("fixture::reexported_fn", 1).unwrap()[0];
+        assert_eq!(reexported.name, "reexported_fn");
+        assert!(
+            std::iter::once(&reexported.path)
+                .chain(&reexported.aliases)
+                .any(|path| path == &["fixture", "reexported_fn"])
+        );
+
+        // globs of modules re-exporting each other
+        let a = krate.search("fixture::cycle_b::A", 1).unwrap()[0];
+        assert_eq!(a.path, ["fixture", "cycle_a", "A"]);
+        let b = krate.search("fixture::cycle_a::B", 1).unwrap()[0];
+        assert_eq!(b.path, ["fixture", "cycle_b", "B"]);
+
+        assert!(
+            krate.search("fixture::prelude::shapes", None).is_none(),
+            "the glob `use` itself must not be indexed"
+        );
+    }
+
+    #[test]
+    fn index_fields_and_assoc_items() {
+        init_logger();
+
+        use rustdoc_types::ItemKind;
+
+        let krate = fixture();
+
+        for (path, kind) in [
+            ("fixture::config::Config::retries", ItemKind::StructField),
+            ("fixture::config::Millis::0", ItemKind::StructField),
+            (
+                "fixture::shapes::Shape::Circle::radius",
+                ItemKind::StructField,
+            ),
+            ("fixture::shapes::Shape::Rect::1", ItemKind::StructField),
+            ("fixture::Bits::float", ItemKind::StructField),
+            (
+                "fixture::config::Config::DEFAULT_TIMEOUT",
+                ItemKind::AssocConst,
+            ),
+            ("fixture::shapes::Area::Output", ItemKind::AssocType),
+        ] {
+            let hits = krate
+                .search(path, 1)
+                .unwrap_or_else(|| panic!("`{path}` is not indexed"));
+            assert_eq!(hits[0].path.join("::"), path);
+            assert_eq!(hits[0].kind, Some(kind), "wrong kind for `{path}`");
+        }
+
+        let hits = krate.search("fixture::config::Config::secret", 1).unwrap();
+        assert_eq!(
+            hits[0].kind,
+            Some(ItemKind::Function),
+            "private fields must not be indexed"
+        );
+    }
+
+    #[test]
+    fn browse_implementations() {
+        init_logger();
+
+        let krate = fixture();
+
+        let greet = krate.search("fixture::Greet", 1).unwrap()[0];
+        let implementors = krate.implementors_of(greet);
+        assert_eq!(implementors.len(), 1);
+        assert_eq!(implementors[0].kind, ImplKind::Blanket);
+        assert_eq!(
+            implementors[0].header,
+            "impl<T: std::fmt::Display> Greet for T"
+        );
+
+        let wrapper = krate.search("fixture::Wrapper", 1).unwrap()[0];
+        let traits = krate.traits_implemented_by(wrapper);
+        let of_kind = |kind: ImplKind| {
+            traits
+                .iter()
+                .filter(|i| i.kind == kind)
+                .map(|i| i.trait_path.as_ref().map_or("", |p| p.last().unwrap()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(of_kind(ImplKind::Inherent), [""]);
+        assert_eq!(of_kind(ImplKind::Trait), ["Deref"]);
+        assert!(of_kind(ImplKind::Synthetic).contains(&"Send"));
+        assert!(!of_kind(ImplKind::Blanket).contains(&"Greet"));
+        assert!(of_kind(ImplKind::Blanket).contains(&"Into"));
+
+        let inherent = &traits[0];
+        assert_eq!(inherent.header, "impl Wrapper");
+        assert_eq!(inherent.items[0].name, "into_inner");
+
+        assert!(krate.traits_implemented_by(greet).is_empty());
+    }
+
+    #[test]
+    fn methods_from_traits_and_deref() {
+        init_logger();
+
+        let krate = fixture();
+
+        let names = |path: &str| {
+            let item = krate.search(path, 1).unwrap()[0];
+            krate
+                .methods_of(item)
+                .into_iter()
+                .map(|method| (method.item.name.clone(), method.origin))
+                .collect::<Vec<_>>()
+        };
+
+        let buffer = vec!["fixture".to_string(), "Buffer".to_string()];
+        let wrapper = names("fixture::Wrapper");
+        assert_eq!(wrapper[0], ("into_inner".into(), MethodOrigin::Inherent));
+        let deref = wrapper.iter().find(|(name, _)| name == "deref").unwrap();
+        assert!(
+            matches!(&deref.1, MethodOrigin::Trait { trait_path } if trait_path.ends_with(&["Deref".to_string()]))
+        );
+        assert!(wrapper.contains(&(
+            "len".into(),
+            MethodOrigin::Deref {
+                target: buffer.clone()
+            }
+        )));
+
+        let area = MethodOrigin::Trait {
+            trait_path: vec!["fixture".into(), "shapes".into(), "Area".into()],
+        };
+        let shape = names("fixture::shapes::Shape");
+        assert!(shape.contains(&("is_empty".into(), MethodOrigin::Inherent)));
+        assert!(shape.contains(&("area".into(), area.clone())));
+        assert!(
+            shape.contains(&("describe".into(), area)),
+            "provided trait methods are callable too"
+        );
+
+        let hits = krate.search_detailed("fixture::Wrapper::len", 1).unwrap();
+        assert_eq!(hits[0].item.path, ["fixture", "Buffer", "len"]);
+        assert_eq!(
+            hits[0].origin,
+            Some(&MethodOrigin::Deref { target: buffer })
+        );
+
+        let hits = krate.search_detailed("fixture::Buffer::len", 1).unwrap();
+        assert_eq!(hits[0].origin, None);
+    }
+
+    #[test]
+    fn blanket_and_synthetic_impls() {
+        init_logger();
+
+        let parsed = Doc::from_json(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/fixture.json"
+        ))
+        .unwrap()
+        .parse()
+        .unwrap();
+
+        let krate = parsed.build_search_index();
+        let hits = krate
+            .search_detailed("fixture::config::Millis::greet", 1)
+            .unwrap();
+        let greet = hits[0].item;
+        assert!(matches!(hits[0].origin, Some(MethodOrigin::Blanket { .. })));
+        assert_eq!(
+            greet.blanket_impl.as_deref(),
+            Some("impl<T: std::fmt::Display> Greet for T")
+        );
+
+        let millis = krate.search("fixture::config::Millis", 1).unwrap()[0];
+        let kinds: Vec<_> = krate
+            .traits_implemented_by(millis)
+            .iter()
+            .map(|i| i.kind)
+            .collect();
+        assert!(kinds.contains(&ImplKind::Blanket));
+        assert!(kinds.contains(&ImplKind::Synthetic));
+
+        let krate = parsed.build_search_index_with(IndexOptions {
+            blanket_impls: false,
+            synthetic_impls: false,
+        });
+        let hits = krate.search("greet", None).unwrap();
+        assert!(
+            hits.iter().all(|item| item.blanket_impl.is_none()),
+            "blanket impl methods must not be indexed"
+        );
+
+        let millis = krate.search("fixture::config::Millis", 1).unwrap()[0];
+        let kinds: Vec<_> = krate
+            .traits_implemented_by(millis)
+            .iter()
+            .map(|i| i.kind)
+            .collect();
+        assert_eq!(kinds, [ImplKind::Trait]);
+        assert!(
+            krate
+                .methods_of(millis)
+                .iter()
+                .all(|method| !matches!(method.origin, MethodOrigin::Blanket { .. }))
+        );
+
+        let greet = krate.search("fixture::Greet", 1).unwrap()[0];
+        assert!(krate.implementors_of(greet).is_empty());
+    }
+
+    #[test]
+    fn module_tree_fixture() {
+        init_logger();
+
+        let parsed = Doc::from_json(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/fixture.json"
+        ))
+        .unwrap()
+        .parse()
+        .unwrap();
+        let tree = parsed.module_tree();
+
+        let root = tree.root();
+        assert_eq!(tree.name(root), Some("fixture"));
+        assert_eq!(tree.parent(root), None);
+
+        let config = tree.find("fixture::config").unwrap();
+        assert_eq!(tree.kind(config), Some(rustdoc_types::ItemKind::Module));
+        assert_eq!(tree.parent(config), Some(root));
+        let names: Vec<_> = tree
+            .children(config)
+            .into_iter()
+            .filter_map(|id| tree.name(id))
+            .collect();
+        assert!(names.contains(&"Config") && names.contains(&"Millis"));
+
+        // `pub use inner::*` lists the items of the private module in the root
+        let names: Vec<_> = tree
+            .children(root)
+            .into_iter()
+            .filter_map(|id| tree.name(id))
+            .collect();
+        assert!(names.contains(&"Reexported") && names.contains(&"reexported_fn"));
+
+        // `pub use crate::shapes::*` in the prelude, through a non-glob re-export of `Config`
+        let shape = tree.find("fixture::prelude::Shape").unwrap();
+        assert_eq!(tree.find("fixture::shapes::Shape"), Some(shape));
+        assert!(tree.find("fixture::prelude::Config").is_some());
+        assert!(tree.find("fixture::nope").is_none());
+
+        let walked = tree.walk();
+        assert_eq!(walked[0], (0, root));
+        assert!(walked.contains(&(2, shape)));
+    }
+
+    #[test]
+    fn canonical_paths() {
+        init_logger();
+
+        let krate = fixture();
+        let item = |path: &str| krate.search(path, 1).unwrap()[0];
+
+        let reexported = item("fixture::Reexported");
+        assert_eq!(
+            reexported.definition_path,
+            ["fixture", "inner", "Reexported"]
+        );
+        assert_eq!(
+            reexported.canonical_path.as_deref(),
+            Some(&["fixture".to_string(), "Reexported".to_string()][..])
+        );
+        assert_eq!(
+            reexported.path_by(PathPreference::Canonical),
+            ["fixture", "Reexported"]
+        );
+
+        let noop = item("fixture::Reexported::noop");
+        assert_eq!(
+            noop.definition_path,
+            ["fixture", "inner", "Reexported", "noop"]
+        );
+        assert_eq!(
+            noop.path_by(PathPreference::Canonical),
+            ["fixture", "Reexported", "noop"]
+        );
+
+        // as short as `fixture::prelude::Config`, the definition path wins the tie
+        let config = item("fixture::config::Config");
+        assert_eq!(
+            config.path_by(PathPreference::Canonical),
+            ["fixture", "config", "Config"]
+        );
+        let b = item("fixture::cycle_b::B");
+        assert_eq!(
+            b.path_by(PathPreference::Canonical),
+            ["fixture", "cycle_b", "B"]
+        );
+        let circle = item("fixture::shapes::Shape::Circle");
+        assert_eq!(
+            circle.canonical_path.as_deref(),
+            Some(&["fixture", "shapes", "Shape", "Circle"].map(String::from)[..])
+        );
+
+        let hidden = item("fixture::hidden_fn");
+        assert_eq!(
+            hidden.canonical_path, None,
+            "doc(hidden) items are not public"
+        );
+        assert_eq!(
+            hidden.path_by(PathPreference::Definition),
+            ["fixture", "hidden_fn"]
+        );
+    }
+
+    #[test]
+    fn item_signatures() {
+        init_logger();
+
+        let krate = fixture();
+        let signature = |path: &str| krate.search(path, 1).unwrap()[0].signature();
+
+        assert_eq!(
+            signature("fixture::client::Client::send").as_deref(),
+            Some("fn send(&self, body: &[u8]) -> Result<usize, String>")
+        );
+        assert_eq!(
+            signature("fixture::dangerous").as_deref(),
+            Some("unsafe fn dangerous(ptr: *const u8) -> u8")
+        );
+        assert_eq!(
+            signature("fixture::Result").as_deref(),
+            Some("type Result<T> = std::result::Result<T, String>")
+        );
+        assert_eq!(
+            signature("fixture::config::Config::retries").as_deref(),
+            Some("retries: u8")
+        );
+        assert_eq!(signature("fixture::config").as_deref(), None);
+    }
+
+    #[test]
+    fn api_diff() {
+        init_logger();
+
+        let version = |path: &str| {
+            Doc::from_json(path)
+                .unwrap()
+                .parse()
+                .unwrap()
+                .build_search_index()
+        };
+        let old = version(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/versioned_v1.json"
+        ));
+        let new = version(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/versioned_v2.json"
+        ));
+
+        assert!(old.diff(&old).is_empty());
+
+        let diff = old.diff(&new);
+        assert!(diff.is_breaking());
+        let changes = |severity: Severity| {
+            diff.changes
+                .iter()
+                .filter(|change| change.severity == severity)
+                .map(|change| (change.path[1..].join("::"), change.change.clone()))
+                .collect::<Vec<_>>()
+        };
+        let breaking = changes(Severity::Breaking);
+        let minor = changes(Severity::Minor);
+
+        let added = |path: &str| (path.to_string(), ChangeKind::Added);
+        assert!(breaking.contains(&("removed".to_string(), ChangeKind::Removed)));
+        assert!(breaking.contains(&(
+            "parse".to_string(),
+            ChangeKind::SignatureChanged {
+                old: "fn parse(input: &str) -> u32".to_string(),
+                new: "fn parse(input: &str) -> u64".to_string(),
+            }
+        )));
+        // exhaustive types and required trait items break downstream code
+        assert!(breaking.contains(&added("Mode::Append")));
+        assert!(breaking.contains(&added("Settings::color")));
+        assert!(breaking.contains(&added("Handler::close")));
+        assert!(minor.contains(&added("Event::Flush")));
+        assert!(minor.contains(&added("Options::safe")));
+        assert!(minor.contains(&added("Handler::flush")));
+        assert!(minor.contains(&added("added")));
+
+        let impl_removed = |path: &str, header: &str| {
+            let header = header.to_string();
+            (path.to_string(), ChangeKind::ImplRemoved { header })
+        };
+        assert!(breaking.contains(&impl_removed("Token", "impl Clone for Token")));
+        assert!(breaking.contains(&impl_removed("Handle", "impl Send for Handle")));
+        assert!(minor.contains(&(
+            "Token".to_string(),
+            ChangeKind::ImplAdded {
+                header: "impl Debug for Token".to_string()
+            }
+        )));
+        assert!(minor.contains(&(
+            "stable".to_string(),
+            ChangeKind::Deprecated {
+                note: Some("use `added` instead".to_string())
+            }
+        )));
+
+        // the private field of `Handle` isn't part of the API, only the lost auto traits are
+        assert!(
+            diff.changes
+                .iter()
+                .filter(|change| change.path[1] == "Handle")
+                .all(|change| matches!(change.change, ChangeKind::ImplRemoved { .. }))
+        );
+    }
+
+    #[test]
+    fn export_pages() {
+        init_logger();
+
+        let krate = fixture();
+        let dir = std::env::temp_dir().join(format!("docsrs-export-{}", std::process::id()));
+        let read = |file: &str| std::fs::read_to_string(dir.join(file)).unwrap();
+
+        let files = krate.export(&dir, ExportFormat::Markdown).unwrap();
+        assert!(files.contains(&dir.join("fixture/index.md")));
+        assert!(files.contains(&dir.join("fixture/shapes/enum.Shape.md")));
+        // re-exported items get their page at their canonical path
+        assert!(files.contains(&dir.join("fixture/struct.Reexported.md")));
+        assert!(
+            !files
+                .iter()
+                .any(|file| file.starts_with(dir.join("fixture/inner")))
+        );
+
+        let root = read("fixture/index.md");
+        assert!(
+            root.contains("- [`Wrapper`](struct.Wrapper.md): Wraps a [`Buffer`](struct.Buffer.md)")
+        );
+        assert!(root.contains("### `unsafe fn dangerous(ptr: *const u8) -> u8`"));
+        assert!(!root.contains("hidden_fn"));
+
+        let config = read("fixture/config/struct.Config.md");
+        assert!(config.starts_with("# Struct `fixture::config::Config`"));
+        assert!(config.contains("[`Client`](../client/struct.Client.md)"));
+        assert!(config.contains("### `fn timeout(&self) -> u32`"));
+        assert!(config.contains("- `impl Default for Config`"));
+        let wrapper = read("fixture/struct.Wrapper.md");
+        assert!(wrapper.contains("## Methods from Deref\n\n### `fn len(&self) -> usize`"));
+
+        let files = krate.export(&dir, ExportFormat::PlainText).unwrap();
+        assert!(files.contains(&dir.join("fixture/shapes/trait.Area.txt")));
+        let area = read("fixture/shapes/trait.Area.txt");
+        assert!(area.starts_with("Trait fixture::shapes::Area\n==========================="));
+        assert!(area.contains("fn area(&self) -> Self::Output"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn item_pages() {
+        init_logger();
+
+        let krate = fixture();
+        let find = |path: &str| {
+            krate
+                .search(path, None)
+                .unwrap()
+                .into_iter()
+                .find(|item| item.path.join("::") == path)
+                .unwrap()
+        };
+
+        let config = krate.page(find("fixture::config::Config"));
+        assert!(config.starts_with("# Struct `fixture::config::Config`"));
+        assert!(config.contains(
+            "## Implementations\n\n### `impl Config`\n\n#### `const DEFAULT_TIMEOUT: u32`"
+        ));
+        assert!(
+            config
+                .contains("#### `fn new() -> Self`\n\nCreates a config with the default timeout.")
+        );
+        assert!(config.contains("- `impl Default for Config`"));
+
+        let old_api = krate.page(find("fixture::old_api"));
+        assert!(old_api.starts_with("# Function `fixture::old_api`\n\n```rust\nfn old_api()\n```"));
+        assert!(old_api.contains("**Deprecated** since 0.1.0: use `new_api` instead"));
+
+        let circle = krate.page(find("fixture::shapes::Shape::Circle"));
+        assert!(circle.starts_with("# Variant `fixture::shapes::Shape::Circle`"));
+        assert!(circle.contains("## Fields\n\n### `radius: f64`"));
+
+        // reference link definitions are rewritten, code is left alone
+        let mut config = find("fixture::client::Client::config").clone();
+        let page = krate.page(&config);
+        assert!(page.contains("[`Config`]: ../config/struct.Config.md\n[new]: struct.Client.md\n"));
+        config.docs =
+            Some("[Self::new], not `[Self::new]`\n\n```text\n[Self::new]\n```".to_string());
+        let page = krate.page(&config);
+        assert!(page.contains(
+            "[Self::new](struct.Client.md), not `[Self::new]`\n\n```text\n[Self::new]\n```"
+        ));
+    }
+
+    #[test]
+    fn item_examples() {
+        init_logger();
+
+        let krate = fixture();
+        let client = krate.search("fixture::client::Client", Some(1)).unwrap()[0];
+        let examples = client.examples();
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].section.as_deref(), Some("Examples"));
+        assert_eq!(
+            examples[0].code(),
+            "let client = Client::new(Default::default());"
+        );
+        assert_eq!(
+            examples[0].full_code(),
+            "use fixture::client::Client;\nlet client = Client::new(Default::default());"
+        );
+
+        let mut item = client.clone();
+        item.docs = Some(
+            "```rust,no_run,edition2021\n##[derive(Debug)]\nstruct A;\n```\n\n\
+             ```text\nnot rust\n```\n\n\
+             # Panics\n\n```should_panic\n#\npanic!();\n```\n\n\
+             ~~~compile_fail,ignore-windows\nlet x: u8 = \"\";\n~~~"
+                .to_string(),
+        );
+        let examples = item.examples();
+        assert_eq!(examples.len(), 3);
+        assert!(examples[0].no_run && !examples[0].ignore);
+        assert_eq!(examples[0].edition.as_deref(), Some("2021"));
+        assert_eq!(examples[0].section, None);
+        assert_eq!(examples[0].code(), "#[derive(Debug)]\nstruct A;");
+        assert!(examples[1].should_panic);
+        assert_eq!(examples[1].section.as_deref(), Some("Panics"));
+        assert_eq!(examples[1].code(), "panic!();");
+        assert_eq!(examples[1].full_code(), "\npanic!();");
+        assert!(examples[2].compile_fail && examples[2].ignore);
+    }
+
+    #[test]
+    fn render_terminal() {
+        let docs = "Sends a [request](Request) to the\nserver and waits for the [`Response`].\n\n\
+                    # Examples\n\n\
+                    ```no_run\n# use fixture::Client;\n##[derive(Debug)]\nstruct Wrapped(Client);\n```\n\n\
+                    ```text\n# not hidden\n```\n\n\
+                    - a list item that is long enough\n  to be wrapped\n1. first\n\n\
+                    [`Response`]: crate::Response";
+        let plain = TerminalOptions {
+            width: Some(30),
+            color: false,
+        };
+        assert_eq!(
+            plain.render(docs),
+            "Sends a request to the server\nand waits for the Response.\n\n\
+             Examples\n========\n\n\
+             \x20   #[derive(Debug)]\n    struct Wrapped(Client);\n\n\
+             \x20   # not hidden\n\n\
+             \x20 • a list item that is long\n    enough to be wrapped\n1. first\n"
+        );
+
+        let colored = TerminalOptions::default().render(docs);
+        assert!(colored.contains("\x1b[4m\x1b[1mExamples\x1b[22m\x1b[24m"));
+        assert!(colored.contains("\x1b[35mstruct\x1b[39m \x1b[33mWrapped\x1b[39m"));
+        assert!(colored.contains("the \x1b[4m\x1b[36mResponse\x1b[39m\x1b[24m."));
+
+        let lines = MarkdownLine::parse(docs);
+        assert_eq!(
+            lines[3],
+            MarkdownLine::Heading {
+                level: 1,
+                text: "Examples"
+            }
+        );
+        let code: Vec<&MarkdownLine> = lines
+            .iter()
+            .filter(|line| matches!(line, MarkdownLine::Code { .. }))
+            .collect();
+        assert_eq!(code.len(), 3);
+        assert_eq!(
+            code[2],
+            &MarkdownLine::Code {
+                line: "# not hidden".to_string(),
+                rust: false
+            }
+        );
+        assert_eq!(lines.last(), Some(&MarkdownLine::Text("")));
+
+        // A code block only ends at a fence of its own character that is at least as long
+        let code = |line: &str| MarkdownLine::Code {
+            line: line.to_string(),
+            rust: true,
+        };
+        assert_eq!(
+            MarkdownLine::parse("```\n~~~\n```\n````\n```\n`````"),
+            [code("~~~"), code("```")]
+        );
+
+        assert_eq!(
+            MarkdownSpan::parse("a * b, [`Vec`][1] and **`x`** `code"),
+            [
+                MarkdownSpan::Text("a * b, "),
+                MarkdownSpan::Link("`Vec`"),
+                MarkdownSpan::Text(" and "),
+                MarkdownSpan::Bold("`x`"),
+                MarkdownSpan::Text(" `code"),
+            ]
+        );
+    }
+
+    #[test]
+    fn iterate_items() {
+        init_logger();
+
+        let krate = fixture();
+        let paths: Vec<&[String]> = krate.items().map(|item| item.path.as_slice()).collect();
+        assert!(paths.is_sorted(), "items must be sorted by path");
+        assert!(paths.len() > 40);
+        assert_eq!(
+            krate.items().map(|item| &item.id).collect::<Vec<_>>(),
+            fixture().items().map(|item| &item.id).collect::<Vec<_>>(),
+            "the order must not depend on the index"
+        );
+
+        let traits: Vec<String> = krate
+            .iter_by_kind(rustdoc_types::ItemKind::Trait)
+            .filter(|item| item.crate_id == 0)
+            .map(|item| item.path.join("::"))
+            .collect();
+        assert_eq!(traits, ["fixture::Greet", "fixture::shapes::Area"]);
+
+        let config = krate.get_by_path("fixture::config::Config").unwrap();
+        assert_eq!(config.kind, Some(rustdoc_types::ItemKind::Struct));
+        // re-exports and glob re-exports
+        assert_eq!(krate.get_by_path("fixture::prelude::Config"), Some(config));
+        let noop = krate.get_by_path("fixture::Reexported::noop").unwrap();
+        assert_eq!(noop.name, "noop");
+        assert!(krate.get_by_path("fixture::config::config").is_none());
+        assert!(krate.get_by_path("fixture::config::Conf").is_none());
+
+        let id = rustdoc_types::Id(config.id.parse().unwrap());
+        assert_eq!(krate.get_by_id(id), Some(config));
+        assert!(krate.get_by_id(rustdoc_types::Id(u32::MAX)).is_none());
+    }
+
+    #[test]
+    fn lookup_exact_paths() {
+        init_logger();
+
+        let krate = fixture();
+        let config = krate.get_by_path("fixture::config::Config").unwrap();
+        for path in [
+            "crate::config::Config",
+            "self::prelude::Config",
+            "::fixture::config::Config",
+        ] {
+            assert_eq!(krate.lookup(path), [config], "{path}");
+        }
+        assert!(krate.lookup("fixture::config::config").is_empty());
+        assert!(krate.lookup("FIXTURE::config::Config").is_empty());
+        assert_eq!(krate.lookup("fixture::config").len(), 1);
+
+        // the field and the method of the same name
+        let timeout = krate.lookup("fixture::config::Config::timeout");
+        let kinds: Vec<_> = timeout.iter().map(|item| item.kind).collect();
+        assert_eq!(kinds.len(), 2);
+        assert!(kinds.contains(&Some(rustdoc_types::ItemKind::StructField)));
+        assert!(kinds.contains(&Some(rustdoc_types::ItemKind::Function)));
+        let hits = krate
+            .search("fixture::config::Config::timeout", None)
+            .unwrap();
+        assert_eq!(hits.len(), 2, "every item at an exact path is a result");
+
+        // generic arguments, with and without turbofish, and methods through `Deref`
+        let len = krate.lookup("crate::Buffer::len");
+        assert_eq!(len.len(), 1);
+        assert_eq!(krate.lookup("fixture::Wrapper<T>::len"), len);
+        assert_eq!(krate.lookup("fixture::Wrapper::<T>::len"), len);
+
+        // members of glob re-exported types at their canonical path
+        let noop = krate.lookup("fixture::Reexported::noop");
+        assert_eq!(noop.len(), 1);
+        assert_eq!(
+            noop[0].canonical_path.as_ref().unwrap().join("::"),
+            "fixture::Reexported::noop"
+        );
+    }
+
+    #[test]
+    fn coverage_fixture() {
+        init_logger();
+
+        let mut parsed = Doc::from_json(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/fixture.json"
+        ))
+        .unwrap()
+        .parse()
+        .unwrap();
+        let report = parsed.coverage();
+        assert_eq!(report.total.documented, 49);
+        assert_eq!(report.total.undocumented(), 4);
+        let by_kind = |kind| report.by_kind.iter().find(|(k, _)| *k == kind).unwrap().1;
+        assert_eq!(by_kind(rustdoc_types::ItemKind::Function).undocumented(), 1);
+        assert_eq!(by_kind(rustdoc_types::ItemKind::Module).undocumented(), 3);
+        let config = &report
+            .by_module
+            .iter()
+            .find(|(m, _)| m == &["fixture", "config"])
+            .unwrap()
+            .1;
+        assert_eq!((config.documented, config.total), (8, 9));
+
+        let issues = |parsed: &Doc<Parsed>, path: &str| -> Vec<DocLint> {
+            parsed
+                .coverage()
+                .issues
+                .into_iter()
+                .filter(|issue| issue.path.join("::") == path)
+                .map(|issue| issue.lint)
+                .collect()
+        };
+        let section = |section: &str| DocLint::MissingSection {
+            section: section.to_string(),
+        };
+        assert_eq!(
+            issues(&parsed, "fixture::undocumented"),
+            [DocLint::Undocumented]
+        );
+        assert_eq!(
+            issues(&parsed, "fixture::dangerous"),
+            [section("Safety"), section("Examples")]
+        );
+        assert_eq!(
+            issues(&parsed, "fixture::client::Client::send"),
+            [section("Examples")]
+        );
+        // `[`Buffer`]` and `[`Client`](crate::client::Client)` resolve
+        assert!(issues(&parsed, "fixture::Wrapper").is_empty());
+        assert!(issues(&parsed, "fixture::config::Config").is_empty());
+        // indexing like `bytes[i]` isn't a link, neither are brackets in code spans
+        assert!(issues(&parsed, "fixture::Buffer").is_empty());
+        // private fields, `#[doc(hidden)]` and `pub(crate)` items aren't counted
+        assert!(issues(&parsed, "fixture::hidden_fn").is_empty());
+        assert!(issues(&parsed, "fixture::internal").is_empty());
+        assert_eq!(report.undocumented().count(), 4);
+
+        let new_api = parsed
+            .0
+            .ast
+            .index
+            .values_mut()
+            .find(|item| item.name.as_deref() == Some("new_api"))
+            .unwrap();
+        new_api.docs = Some(
+            "Replaces [`old_api`], see [the guide](https://example.com) and [`Missing`].\n\n\
+             Panics if called twice, unlike `v[i]` or [0, 1].\n\n\
+             # Examples\n\n```\n[`Ignored`];\n```\n\n[`old_api`]: crate::old_api"
+                .to_string(),
+        );
+        new_api.links = [("crate::old_api".to_string(), rustdoc_types::Id(0))].into();
+        assert_eq!(
+            issues(&parsed, "fixture::new_api"),
+            [
+                section("Panics"),
+                DocLint::BrokenLink {
+                    link: "`Missing`".to_string()
+                }
+            ]
+        );
+
+        // saying that it doesn't panic needs no `# Panics` section
+        let new_api = parsed
+            .0
+            .ast
+            .index
+            .values_mut()
+            .find(|item| item.name.as_deref() == Some("new_api"))
+            .unwrap();
+        new_api.docs = Some(
+            "Never panics, unlike `old_api`.\n\nIt does not panic and won’t ever panic.\n\n\
+             # Examples\n\n```\nnew_api();\n```"
+                .to_string(),
+        );
+        assert!(issues(&parsed, "fixture::new_api").is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serialize_items() {
+        init_logger();
+
+        let krate = fixture();
+        let hits = krate
+            .search_detailed("fixture::config::Config", Some(1))
+            .unwrap();
+        let hit = serde_json::to_value(&hits[0]).unwrap();
+        assert_eq!(hit["item"]["name"], "Config");
+        assert_eq!(hit["item"]["kind"], "struct");
+        assert_eq!(hit["matched_key"], "fixture::config::Config");
+
+        let item: Item = serde_json::from_value(hit["item"].clone()).unwrap();
+        assert_eq!(&item, hits[0].item);
+
+        let origin = MethodOrigin::Deref {
+            target: vec!["fixture".to_string(), "Buffer".to_string()],
+        };
+        assert_eq!(
+            serde_json::to_value(&origin).unwrap(),
+            serde_json::json!({"deref": {"target": ["fixture", "Buffer"]}})
+        );
+    }
+
     #[test]
     #[cfg(feature = "fetch")]
     fn fetch_tokio() {
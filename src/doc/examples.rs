@@ -0,0 +1,147 @@
+use super::markdown::{fence_attributes, is_rust_fence, visible_rust_line};
+use crate::Item;
+
+/// A Rust code block of an item's docs, returned by [`Item::examples`].
+///
+/// rustdoc compiles and runs these as doctests, the flags tell how.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Example {
+    /// The code as written in the docs, with the hidden lines (`# use std::fmt;`)
+    pub source: String,
+    /// The heading the code block is under, e.g. `Examples`
+    pub section: Option<String>,
+    /// `ignore` (or `ignore-<target>`): not compiled
+    pub ignore: bool,
+    /// `no_run`: compiled but not run
+    pub no_run: bool,
+    /// `should_panic`: run and expected to panic
+    pub should_panic: bool,
+    /// `compile_fail`: expected to fail to compile
+    pub compile_fail: bool,
+    /// The edition of an `edition2021` style attribute, e.g. `2021`
+    pub edition: Option<String>,
+}
+
+impl Example {
+    /// The code as rustdoc shows it, without the hidden lines
+    pub fn code(&self) -> String {
+        self.source
+            .lines()
+            .filter_map(visible_rust_line)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The code as rustdoc compiles it, with the hidden lines unhidden
+    pub fn full_code(&self) -> String {
+        self.source
+            .lines()
+            .map(|line| {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                let trimmed = line.trim_start();
+                if trimmed == "#" {
+                    String::new()
+                } else if let Some(hidden) = trimmed.strip_prefix("# ") {
+                    format!("{indent}{hidden}")
+                } else if let Some(rest) = trimmed.strip_prefix("##") {
+                    format!("{indent}#{rest}")
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Item {
+    /// Extracts the Rust code blocks of the item's docs
+    ///
+    /// Code blocks without a language are Rust, like rustdoc treats them. Blocks in
+    /// other languages (`text`, `sh`, ...) are left out.
+    ///
+    /// # Returns
+    ///
+    /// The examples in the order of the docs, empty if the item has no docs.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// let doc = Doc::from_json("path/to/docs.json")?.parse()?.build_search_index();
+    /// let spawn = doc.search("tokio::spawn", Some(1)).unwrap()[0];
+    /// for example in spawn.examples().iter().filter(|example| !example.ignore) {
+    ///     println!("{}", example.code());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn examples(&self) -> Vec<Example> {
+        let Some(docs) = self.docs.as_deref() else {
+            return Vec::new();
+        };
+
+        let mut examples = Vec::new();
+        let mut section = None;
+        // The example of the Rust code block we're in, `Some(None)` in other code blocks
+        let mut fence: Option<Option<Example>> = None;
+        let mut lines = Vec::new();
+        for line in docs.lines() {
+            let trimmed = line.trim_start();
+            let Some(info) = trimmed
+                .strip_prefix("```")
+                .or_else(|| trimmed.strip_prefix("~~~"))
+            else {
+                match &fence {
+                    Some(_) => lines.push(line),
+                    None if trimmed.starts_with('#') => {
+                        let heading = trimmed.trim_start_matches('#');
+                        if heading.starts_with(' ') {
+                            section = Some(heading.trim().to_string());
+                        }
+                    }
+                    None => {}
+                }
+                continue;
+            };
+
+            match fence.take() {
+                Some(example) => {
+                    if let Some(mut example) = example {
+                        example.source = lines.join("\n");
+                        examples.push(example);
+                    }
+                    lines.clear();
+                }
+                None => {
+                    fence = Some(is_rust_fence(info).then(|| example(info, section.as_deref())))
+                }
+            }
+        }
+        examples
+    }
+}
+
+/// An example without code, with the flags of its info string
+fn example(info: &str, section: Option<&str>) -> Example {
+    let mut example = Example {
+        section: section.map(String::from),
+        ..Example::default()
+    };
+    for attr in fence_attributes(info) {
+        match attr {
+            "ignore" => example.ignore = true,
+            "no_run" => example.no_run = true,
+            "should_panic" => example.should_panic = true,
+            "compile_fail" => example.compile_fail = true,
+            attr if attr.starts_with("ignore-") => example.ignore = true,
+            attr if attr.starts_with("edition") => {
+                example.edition = Some(attr["edition".len()..].to_string());
+            }
+            _ => {}
+        }
+    }
+    example
+}
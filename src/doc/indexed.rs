@@ -1,13 +1,85 @@
+mod diff;
+mod export;
+mod hit;
+mod impls;
+mod items;
+mod methods;
+mod rank;
 mod search;
 
+pub use diff::{ApiChange, ApiDiff, ChangeKind, Severity};
+pub use export::ExportFormat;
+pub use hit::SearchHit;
+pub(crate) use impls::is_blanket_impl;
+pub use impls::{ImplKind, Implementation};
+pub use methods::{Method, MethodOrigin};
+#[cfg(feature = "nucleo")]
+pub use rank::NucleoRanker;
+pub(crate) use rank::is_doc_hidden;
+pub use rank::{
+    Candidate, KindWeights, LastSegmentBoost, Penalize, PrefixBoost, Ranker, SkimRanker,
+};
+
 use super::Doc;
 use crate::{Error, Item};
-use std::{collections::HashMap, fs::OpenOptions, io::Write, path::Path};
+use rustdoc_types::Impl;
+use std::{
+    collections::{HashMap, HashSet},
+    fs::OpenOptions,
+    io::Write,
+    path::Path,
+    sync::OnceLock,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct SearchKey {
     pub(crate) id: String,
     pub(crate) key: String,
+    /// `key` lowercased once at index time so queries don't have to
+    pub(crate) lower: String,
+    /// Character classes present in `lower`, see [`char_mask`]
+    pub(crate) mask: u64,
+    /// How the method is reachable on the type in `key`, `None` for the item's own paths
+    pub(crate) origin: Option<MethodOrigin>,
+}
+
+impl SearchKey {
+    pub(crate) fn new(id: String, key: String) -> Self {
+        let lower = key.to_lowercase();
+        let mask = char_mask(&lower);
+        Self {
+            id,
+            key,
+            lower,
+            mask,
+            origin: None,
+        }
+    }
+
+    pub(crate) fn with_origin(mut self, origin: MethodOrigin) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+}
+
+/// Builds a bitmask of the character classes occurring in `s`.
+///
+/// Every character of a fuzzy query has to appear somewhere in a matching key,
+/// so a key whose mask doesn't cover the query's mask can be skipped without
+/// running the matcher. ASCII letters and digits get a bit each, `:` and `_`
+/// get their own bit and everything else shares one.
+pub(crate) fn char_mask(s: &str) -> u64 {
+    s.chars().fold(0, |mask, c| {
+        let bit = match c {
+            'a'..='z' => c as u32 - 'a' as u32,
+            'A'..='Z' => c as u32 - 'A' as u32,
+            '0'..='9' => 26 + (c as u32 - '0' as u32),
+            ':' => 36,
+            '_' => 37,
+            _ => 38,
+        };
+        mask | (1 << bit)
+    })
 }
 
 /// Represents indexed documentation data with fuzzy search capabilities.
@@ -19,15 +91,106 @@ pub(crate) struct SearchKey {
 pub struct Indexed {
     pub(crate) search_index: Vec<SearchKey>,
     items: HashMap<String, Item>,
-    matcher: fuzzy_matcher::skim::SkimMatcherV2,
+    /// All impl blocks of the crate, for browsing trait implementations
+    impls: HashMap<String, Impl>,
+    /// Fully qualified paths of all items the crate refers to, including external ones
+    paths: HashMap<String, Vec<String>>,
+    /// The ids of the named items in the order of [`Doc::items`]
+    sorted: Vec<String>,
+    /// The positions in `search_index` of every key, for [`Doc::lookup`]
+    exact: HashMap<String, Vec<usize>>,
+    /// The name of the crate's root module
+    crate_name: Option<String>,
+    ranker: SkimRanker,
+    /// The layout of the Markdown pages, built on the first [`Doc::page`]
+    markdown_layout: OnceLock<export::Layout>,
 }
 
 impl Doc<Indexed> {
-    pub(super) fn new(search_index: Vec<SearchKey>, items: HashMap<String, Item>) -> Self {
-        Self(Indexed {
+    pub(super) fn new(
+        mut search_index: Vec<SearchKey>,
+        items: HashMap<String, Item>,
+        impls: HashMap<String, Impl>,
+        paths: HashMap<String, Vec<String>>,
+    ) -> Self {
+        // Keys that can never be returned are dropped up front, so the search
+        // loop doesn't have to look them up on every query
+        search_index.retain(|key| items.get(&key.id).is_some_and(|item| !item.name.is_empty()));
+        let canonical_keys = items::canonical_keys(&search_index, &items);
+        search_index.extend(canonical_keys);
+
+        let sorted = items::sorted_ids(&items);
+        let crate_name = items::crate_name(&items);
+        let mut doc = Self(Indexed {
             search_index,
             items,
-            matcher: fuzzy_matcher::skim::SkimMatcherV2::default(),
+            impls,
+            paths,
+            sorted,
+            exact: HashMap::new(),
+            crate_name,
+            ranker: SkimRanker::default(),
+            markdown_layout: OnceLock::new(),
+        });
+
+        let method_keys = doc.method_keys();
+        doc.0.search_index.extend(method_keys);
+        doc.0.exact = items::exact_keys(&doc.0.search_index);
+        doc
+    }
+
+    /// Resolves the intra-doc links of an item
+    ///
+    /// # Arguments
+    ///
+    /// - `item` - The item whose docs contain the links
+    ///
+    /// # Returns
+    ///
+    /// The link texts as written in the docs (e.g. `` `Config::new` ``) with the
+    /// linked items, sorted by text. Links to items of other crates are left out.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// let doc = Doc::from_json("path/to/docs.json")?.parse()?.build_search_index();
+    /// let item = doc.search("tokio::spawn", Some(1)).unwrap()[0];
+    /// for (text, linked) in doc.links_of(item) {
+    ///     println!("{text} -> {}", linked.path.join("::"));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn links_of<'a>(&'a self, item: &'a Item) -> Vec<(&'a str, &'a Item)> {
+        let mut links: Vec<(&str, &Item)> = item
+            .links
+            .iter()
+            .filter_map(|(text, id)| Some((text.as_str(), self.0.items.get(id)?)))
+            .collect();
+        links.sort_by(|a, b| a.0.cmp(b.0));
+        links
+    }
+
+    /// The publicly reachable items of the crate, i.e. the ones with a canonical path.
+    ///
+    /// Items of trait impls and blanket impls are left out, they are part of their impl
+    /// rather than of the API of the type or trait.
+    pub(super) fn public_items(&self) -> impl Iterator<Item = &Item> {
+        let trait_impl_items: HashSet<String> = self
+            .0
+            .impls
+            .values()
+            .filter(|impl_block| impl_block.trait_.is_some())
+            .flat_map(|impl_block| impl_block.items.iter().map(|id| id.0.to_string()))
+            .collect();
+
+        self.0.items.values().filter(move |item| {
+            item.crate_id == 0
+                && item.canonical_path.is_some()
+                && item.blanket_impl.is_none()
+                && !trait_impl_items.contains(&item.id)
         })
     }
 
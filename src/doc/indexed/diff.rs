@@ -0,0 +1,299 @@
+use super::{Doc, ImplKind, Indexed};
+use crate::Item;
+use rustdoc_types::{Attribute, ItemEnum, ItemKind, StructKind};
+use std::collections::{HashMap, HashSet};
+
+/// The public items of a crate by canonical path and kind
+type Api<'a> = HashMap<(&'a [String], Option<ItemKind>), &'a Item>;
+
+/// How a change affects users of the crate, following cargo's semver rules.
+///
+/// The variants are ordered by their impact, breaking changes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Severity {
+    /// Code compiling against the old version may fail to compile against the new one,
+    /// requires a major version bump
+    Breaking,
+    /// Adds to the API without breaking existing code, requires a minor version bump
+    Minor,
+}
+
+/// What changed about an item.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ChangeKind {
+    /// The item is new
+    Added,
+    /// The item was removed or is no longer public
+    Removed,
+    /// The signature of the item changed, see [`Item::signature`]
+    SignatureChanged {
+        /// The signature in the old version
+        old: String,
+        /// The signature in the new version
+        new: String,
+    },
+    /// The type implements a trait it didn't implement before
+    ImplAdded {
+        /// The header of the new impl, e.g. `impl Debug for Token`
+        header: String,
+    },
+    /// The type no longer implements a trait, including auto traits like `Send`
+    ImplRemoved {
+        /// The header of the removed impl
+        header: String,
+    },
+    /// The item was deprecated
+    Deprecated {
+        /// The deprecation note, if any
+        note: Option<String>,
+    },
+}
+
+/// A single change of the public API, see [`ApiDiff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApiChange {
+    /// The canonical path of the changed item
+    pub path: Vec<String>,
+    /// The kind of the changed item
+    pub kind: Option<ItemKind>,
+    /// What changed
+    pub change: ChangeKind,
+    /// Whether the change is breaking
+    pub severity: Severity,
+}
+
+/// The differences between the public APIs of two versions of a crate, returned by [`Doc::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApiDiff {
+    /// All changes, breaking changes first, then sorted by path
+    pub changes: Vec<ApiChange>,
+}
+
+impl ApiDiff {
+    /// The changes that require a major version bump
+    pub fn breaking(&self) -> impl Iterator<Item = &ApiChange> {
+        self.changes
+            .iter()
+            .filter(|change| change.severity == Severity::Breaking)
+    }
+
+    /// The changes that require a minor version bump
+    pub fn minor(&self) -> impl Iterator<Item = &ApiChange> {
+        self.changes
+            .iter()
+            .filter(|change| change.severity == Severity::Minor)
+    }
+
+    /// Whether any change is breaking
+    pub fn is_breaking(&self) -> bool {
+        self.breaking().next().is_some()
+    }
+
+    /// Whether the public APIs are the same
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl Doc<Indexed> {
+    /// Compares the public API of this version of a crate with a newer one
+    ///
+    /// Items are matched by their canonical path and kind, so moving an item to
+    /// another module while re-exporting it at the old path isn't a change. Besides
+    /// added and removed items this reports changed signatures, added and removed
+    /// trait impls (including auto traits like `Send`) and new deprecations.
+    ///
+    /// Changes are classified according to cargo's semver rules: removals, signature
+    /// changes and removed impls are breaking, so are new variants of enums and new
+    /// fields of structs that aren't `#[non_exhaustive]` and new required items of
+    /// traits. Everything else is minor. Signature changes that are compatible, like
+    /// loosening a bound, are reported as breaking too.
+    ///
+    /// # Arguments
+    ///
+    /// - `newer` - The newer version of the crate
+    ///
+    /// # Returns
+    ///
+    /// An [`ApiDiff`] with the changes, empty if the public APIs are the same.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// let load = |version| -> Result<_, docsrs::Error> {
+    ///     Ok(Doc::from_docs("tokio", version)?.fetch()?.decompress()?.parse()?.build_search_index())
+    /// };
+    /// let diff = load("1.37.0")?.diff(&load("1.38.0")?);
+    /// for change in diff.breaking() {
+    ///     println!("{} {:?}", change.path.join("::"), change.change);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diff(&self, newer: &Doc<Indexed>) -> ApiDiff {
+        let old_api = self.public_api();
+        let new_api = newer.public_api();
+
+        let mut changes = Vec::new();
+        let mut removed = Vec::new();
+        for (key, old_item) in &old_api {
+            let Some(new_item) = new_api.get(key) else {
+                removed.push(change(old_item, ChangeKind::Removed, Severity::Breaking));
+                continue;
+            };
+
+            if let (Some(old), Some(new)) = (old_item.signature(), new_item.signature())
+                && old != new
+            {
+                let change_kind = ChangeKind::SignatureChanged { old, new };
+                changes.push(change(new_item, change_kind, Severity::Breaking));
+            }
+
+            if old_item.deprecation.is_none()
+                && let Some(deprecation) = &new_item.deprecation
+            {
+                let note = deprecation.note.clone();
+                changes.push(change(
+                    new_item,
+                    ChangeKind::Deprecated { note },
+                    Severity::Minor,
+                ));
+            }
+
+            let old_impls = self.trait_impl_headers(old_item);
+            let new_impls = newer.trait_impl_headers(new_item);
+            for header in old_impls.difference(&new_impls) {
+                let change_kind = ChangeKind::ImplRemoved {
+                    header: header.clone(),
+                };
+                changes.push(change(new_item, change_kind, Severity::Breaking));
+            }
+            for header in new_impls.difference(&old_impls) {
+                let change_kind = ChangeKind::ImplAdded {
+                    header: header.clone(),
+                };
+                changes.push(change(new_item, change_kind, Severity::Minor));
+            }
+        }
+
+        let added = new_api
+            .iter()
+            .filter(|(key, _)| !old_api.contains_key(key))
+            .map(|(_, item)| change(item, ChangeKind::Added, addition_severity(item, &new_api)))
+            .collect();
+
+        // The items of a removed or added type or module are implied
+        changes.extend(without_children(removed));
+        changes.extend(without_children(added));
+
+        changes.sort_by_cached_key(|change| {
+            (
+                change.severity,
+                change.path.clone(),
+                format!("{:?}", change.kind),
+                change.change.clone(),
+            )
+        });
+        ApiDiff { changes }
+    }
+
+    /// The public items of the crate by canonical path and kind
+    fn public_api(&self) -> Api<'_> {
+        self.public_items()
+            .filter_map(|item| Some(((item.canonical_path.as_deref()?, item.kind), item)))
+            .collect()
+    }
+
+    /// The headers of the trait impls of a type, negative impls like `impl !Send` are left out
+    fn trait_impl_headers(&self, type_item: &Item) -> HashSet<String> {
+        self.traits_implemented_by(type_item)
+            .into_iter()
+            .filter(|implementation| {
+                implementation.kind != ImplKind::Inherent && !implementation.inner.is_negative
+            })
+            .map(|implementation| implementation.header)
+            .collect()
+    }
+}
+
+fn change(item: &Item, change: ChangeKind, severity: Severity) -> ApiChange {
+    ApiChange {
+        path: item.canonical_path.clone().unwrap_or_default(),
+        kind: item.kind,
+        change,
+        severity,
+    }
+}
+
+/// Drops the changes whose path starts with the path of another change
+fn without_children(changes: Vec<ApiChange>) -> Vec<ApiChange> {
+    let paths: HashSet<&[String]> = changes.iter().map(|change| &change.path[..]).collect();
+    let keep: Vec<bool> = changes
+        .iter()
+        .map(|change| (1..change.path.len()).all(|len| !paths.contains(&change.path[..len])))
+        .collect();
+
+    changes
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(change, keep)| keep.then_some(change))
+        .collect()
+}
+
+/// Whether adding `item` breaks code using its parent
+///
+/// New variants break exhaustive matches, new fields break struct literals and
+/// patterns and new required trait items break implementations of the trait.
+fn addition_severity(item: &Item, api: &Api) -> Severity {
+    let Some((_, parent_path)) = item
+        .canonical_path
+        .as_deref()
+        .and_then(<[String]>::split_last)
+    else {
+        return Severity::Minor;
+    };
+    let parent = |kind| api.get(&(parent_path, Some(kind))).copied();
+
+    let breaking = match &item.inner {
+        ItemEnum::Variant(_) => parent(ItemKind::Enum).is_some_and(|e| !is_non_exhaustive(e)),
+        ItemEnum::StructField(_) => {
+            parent(ItemKind::Struct).is_some_and(|s| {
+                let all_public = match &s.inner {
+                    ItemEnum::Struct(s) => match &s.kind {
+                        StructKind::Plain {
+                            has_stripped_fields,
+                            ..
+                        } => !has_stripped_fields,
+                        StructKind::Tuple(fields) => fields.iter().all(Option::is_some),
+                        StructKind::Unit => true,
+                    },
+                    _ => false,
+                };
+                all_public && !is_non_exhaustive(s)
+            }) || parent(ItemKind::Variant).is_some_and(|v| !is_non_exhaustive(v))
+        }
+        ItemEnum::Function(function) => !function.has_body && parent(ItemKind::Trait).is_some(),
+        ItemEnum::AssocType { type_: None, .. } | ItemEnum::AssocConst { value: None, .. } => {
+            parent(ItemKind::Trait).is_some()
+        }
+        _ => false,
+    };
+
+    if breaking {
+        Severity::Breaking
+    } else {
+        Severity::Minor
+    }
+}
+
+fn is_non_exhaustive(item: &Item) -> bool {
+    item.attributes.contains(&Attribute::NonExhaustive)
+}
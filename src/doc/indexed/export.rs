@@ -0,0 +1,828 @@
+use super::{Doc, ImplKind, Indexed, MethodOrigin};
+use crate::{
+    Error, Item,
+    doc::markdown::{is_link_definition, is_rust_fence},
+};
+use rustdoc_types::{ItemEnum, ItemKind, StructKind, VariantKind};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The format of the pages written by [`Doc::export`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ExportFormat {
+    /// Markdown files (`.md`) with relative links between the pages
+    #[default]
+    Markdown,
+    /// Plain text files (`.txt`), links are written as `text (path)`
+    PlainText,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::PlainText => "txt",
+        }
+    }
+}
+
+/// The sections of a module page in order, with the kinds of items they list.
+/// Items of the first group get pages of their own, the others are rendered in full.
+const MODULE_SECTIONS: [(&str, ItemKind); 11] = [
+    ("Modules", ItemKind::Module),
+    ("Structs", ItemKind::Struct),
+    ("Enums", ItemKind::Enum),
+    ("Unions", ItemKind::Union),
+    ("Traits", ItemKind::Trait),
+    ("Functions", ItemKind::Function),
+    ("Type Aliases", ItemKind::TypeAlias),
+    ("Constants", ItemKind::Constant),
+    ("Statics", ItemKind::Static),
+    ("Macros", ItemKind::Macro),
+    ("Trait Aliases", ItemKind::TraitAlias),
+];
+
+impl Doc<Indexed> {
+    /// Writes the documentation as one page per module and type
+    ///
+    /// The pages mirror the module structure of the crate's canonical paths, e.g.
+    /// `tokio/sync/index.md` for the module `tokio::sync` and `tokio/sync/struct.Mutex.md`
+    /// for `tokio::sync::Mutex`. Module pages list their items and contain the functions,
+    /// constants and macros of the module. Type and trait pages contain the signature,
+    /// docs, fields or variants, methods and trait impls. Intra-doc links are rewritten
+    /// to relative links to the page of the item (or the page it is part of).
+    ///
+    /// Only publicly reachable items are exported, see [`Item::canonical_path`].
+    ///
+    /// # Arguments
+    ///
+    /// - `dir` - The directory to write the pages to, created if it doesn't exist
+    /// - `format` - Whether to write Markdown or plain text
+    ///
+    /// # Returns
+    ///
+    /// The paths of the written files, sorted.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::{Doc, ExportFormat};
+    /// let doc = Doc::from_json("path/to/docs.json")?.parse()?.build_search_index();
+    /// let files = doc.export("docs", ExportFormat::Markdown)?;
+    /// println!("wrote {} pages", files.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export<P: AsRef<Path>>(
+        &self,
+        dir: P,
+        format: ExportFormat,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let exporter = Exporter::new(self, format);
+
+        let mut files = Vec::new();
+        for (segments, item) in exporter.pages() {
+            let markdown = exporter.render_page(item, segments);
+            let content = match format {
+                ExportFormat::Markdown => markdown,
+                ExportFormat::PlainText => to_plain_text(&markdown),
+            };
+
+            let path: PathBuf = std::iter::once(dir.as_ref().to_path_buf())
+                .chain(segments.iter().map(PathBuf::from))
+                .collect();
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, content)?;
+            files.push(path);
+        }
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Renders the page of an item as Markdown, the way [`Doc::export`] writes it
+    ///
+    /// Unlike the export this works for every item. Items without a page of their
+    /// own, like functions, methods and fields, get a page with their signature,
+    /// deprecation and docs. Variants list their fields.
+    /// Which items get pages of their own is worked out on the first call and kept
+    /// for the following ones.
+    ///
+    /// # Arguments
+    ///
+    /// - `item` - The item to render, usually found with [`Doc::search`]
+    ///
+    /// # Returns
+    ///
+    /// The Markdown page. Intra-doc links are relative to the page the item is
+    /// exported to, or the page it is part of.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// let doc = Doc::from_json("path/to/docs.json")?.parse()?.build_search_index();
+    /// let mutex = doc.search("tokio::sync::Mutex", Some(1)).unwrap()[0];
+    /// println!("{}", doc.page(mutex));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn page(&self, item: &Item) -> String {
+        let exporter = Exporter::new(self, ExportFormat::Markdown);
+        let from = item
+            .canonical_path
+            .as_deref()
+            .and_then(|path| exporter.page_of(path))
+            .cloned()
+            .unwrap_or_default();
+        exporter.render_page(item, &from)
+    }
+}
+
+/// Which items get pages and where, built once per export and kept by
+/// [`Indexed`] for the Markdown pages of [`Doc::page`]
+#[derive(Debug, Clone, Default)]
+pub(super) struct Layout {
+    /// The ids of the items with a page of their own and the file of the page
+    pages: Vec<(Vec<String>, String)>,
+    /// Page files by the canonical path of their item
+    files: HashMap<Vec<String>, Vec<String>>,
+    /// The ids of public items by the canonical path of their parent, sorted by name
+    children: HashMap<Vec<String>, Vec<String>>,
+}
+
+impl Layout {
+    fn new(doc: &Doc<Indexed>, format: ExportFormat) -> Self {
+        let mut pages = Vec::new();
+        let mut files = HashMap::new();
+        let mut children: HashMap<&[String], Vec<&Item>> = HashMap::new();
+        for item in doc.public_items() {
+            let Some(path) = item.canonical_path.as_deref() else {
+                continue;
+            };
+            if let Some((_, parent)) = path.split_last() {
+                children.entry(parent).or_default().push(item);
+            }
+            if let Some(file) = page_file(item, format) {
+                files.insert(path.to_vec(), file.clone());
+                pages.push((file, item.id.clone()));
+            }
+        }
+        let children = children
+            .into_iter()
+            .map(|(parent, mut items)| {
+                items.sort_by(|a, b| a.name.cmp(&b.name));
+                let ids = items.into_iter().map(|item| item.id.clone()).collect();
+                (parent.to_vec(), ids)
+            })
+            .collect();
+
+        Self {
+            pages,
+            files,
+            children,
+        }
+    }
+}
+
+struct Exporter<'a> {
+    doc: &'a Doc<Indexed>,
+    format: ExportFormat,
+    layout: Cow<'a, Layout>,
+}
+
+impl<'a> Exporter<'a> {
+    fn new(doc: &'a Doc<Indexed>, format: ExportFormat) -> Self {
+        let layout = match format {
+            ExportFormat::Markdown => Cow::Borrowed(
+                doc.0
+                    .markdown_layout
+                    .get_or_init(|| Layout::new(doc, format)),
+            ),
+            ExportFormat::PlainText => Cow::Owned(Layout::new(doc, format)),
+        };
+        Self {
+            doc,
+            format,
+            layout,
+        }
+    }
+
+    /// The items with a page of their own and the file of the page
+    fn pages(&self) -> impl Iterator<Item = (&Vec<String>, &'a Item)> {
+        let items = &self.doc.0.items;
+        self.layout
+            .pages
+            .iter()
+            .filter_map(move |(file, id)| Some((file, items.get(id)?)))
+    }
+
+    /// The public items whose parent is at `path`, sorted by name
+    fn children(&self, path: &[String]) -> Vec<&'a Item> {
+        let ids = self.layout.children.get(path).map(Vec::as_slice);
+        ids.unwrap_or_default()
+            .iter()
+            .filter_map(|id| self.doc.0.items.get(id))
+            .collect()
+    }
+
+    /// Renders the page of an item, `from` is the file links are relative to
+    fn render_page(&self, item: &'a Item, from: &[String]) -> String {
+        let path = item.canonical_path.as_deref().unwrap_or(&item.path);
+
+        let mut page = format!("# {} `{}`\n", page_title(item, path), path.join("::"));
+        if let Some(signature) = item.signature() {
+            page.push_str(&format!("\n```rust\n{signature}\n```\n"));
+        }
+        if let Some(deprecation) = deprecation_note(item) {
+            page.push_str(&format!("\n{deprecation}\n"));
+        }
+        if let Some(docs) = self.docs(item, from, 1) {
+            page.push_str(&format!("\n{docs}\n"));
+        }
+
+        match &item.inner {
+            ItemEnum::Module(_) => self.module_sections(path, from, &mut page),
+            ItemEnum::Struct(s) => {
+                let fields = match &s.kind {
+                    StructKind::Plain { fields, .. } => fields.iter().collect(),
+                    StructKind::Tuple(fields) => fields.iter().flatten().collect(),
+                    StructKind::Unit => Vec::new(),
+                };
+                self.item_section("Fields", &self.items(fields), from, &mut page);
+                self.type_sections(item, from, &mut page);
+            }
+            ItemEnum::Union(u) => {
+                self.item_section("Fields", &self.items(&u.fields), from, &mut page);
+                self.type_sections(item, from, &mut page);
+            }
+            ItemEnum::Variant(v) => {
+                let fields = match &v.kind {
+                    VariantKind::Plain => Vec::new(),
+                    VariantKind::Tuple(fields) => self.items(fields.iter().flatten()),
+                    VariantKind::Struct { fields, .. } => self.items(fields),
+                };
+                self.item_section("Fields", &fields, from, &mut page);
+            }
+            ItemEnum::Enum(e) => {
+                self.variant_section(&self.items(&e.variants), from, &mut page);
+                self.type_sections(item, from, &mut page);
+            }
+            ItemEnum::Trait(t) => {
+                let (required, provided): (Vec<_>, Vec<_>) = self
+                    .items(&t.items)
+                    .into_iter()
+                    .partition(|item| is_required(item));
+                self.item_section("Required Items", &required, from, &mut page);
+                self.item_section("Provided Items", &provided, from, &mut page);
+                let implementors: Vec<String> = self
+                    .doc
+                    .implementors_of(item)
+                    .into_iter()
+                    .map(|implementation| implementation.header)
+                    .collect();
+                list_section("Implementors", &implementors, &mut page);
+            }
+            _ => {}
+        }
+        page
+    }
+
+    /// Lists the items with pages and renders the others in full
+    fn module_sections(&self, path: &[String], from: &[String], page: &mut String) {
+        let children = self.children(path);
+        for (heading, kind) in MODULE_SECTIONS {
+            let items: Vec<&Item> = children
+                .iter()
+                .copied()
+                .filter(|child| child.kind == Some(kind) && !is_reexport(child))
+                .collect();
+            if items.is_empty() {
+                continue;
+            }
+
+            if page_file(items[0], self.format).is_some() {
+                let entries: Vec<String> = items
+                    .iter()
+                    .map(|item| {
+                        let link = self.link(item, from).unwrap_or_default();
+                        match self.summary(item, from) {
+                            Some(summary) => format!("[`{}`]({link}): {summary}", item.name),
+                            None => format!("[`{}`]({link})", item.name),
+                        }
+                    })
+                    .collect();
+                list_section(heading, &entries, page);
+            } else {
+                self.item_section(heading, &items, from, page);
+            }
+        }
+
+        // Re-exports of other crates' items, which have no page in this crate
+        let reexports: Vec<String> = children
+            .iter()
+            .filter_map(|child| match &child.inner {
+                ItemEnum::Use(u) => Some(format!("`pub use {}`", u.source)),
+                _ => None,
+            })
+            .collect();
+        list_section("Re-exports", &reexports, page);
+    }
+
+    /// Methods and trait impls of a struct, enum or union
+    fn type_sections(&self, item: &'a Item, from: &[String], page: &mut String) {
+        let implementations = self.doc.traits_implemented_by(item);
+
+        let inherent: Vec<_> = implementations
+            .iter()
+            .filter(|implementation| {
+                implementation.kind == ImplKind::Inherent && !implementation.items.is_empty()
+            })
+            .collect();
+        if !inherent.is_empty() {
+            page.push_str("\n## Implementations\n");
+            for implementation in inherent {
+                page.push_str(&format!("\n### `{}`\n", implementation.header));
+                for item in &implementation.items {
+                    page.push_str(&self.render_item(item, from, 4));
+                }
+            }
+        }
+
+        let deref: Vec<&Item> = self
+            .doc
+            .methods_of(item)
+            .into_iter()
+            .filter(|method| matches!(method.origin, MethodOrigin::Deref { .. }))
+            .map(|method| method.item)
+            .collect();
+        self.item_section("Methods from Deref", &deref, from, page);
+
+        for (heading, kinds) in [
+            ("Trait Implementations", &[ImplKind::Trait][..]),
+            (
+                "Auto Trait Implementations",
+                &[ImplKind::Auto, ImplKind::Synthetic],
+            ),
+            ("Blanket Implementations", &[ImplKind::Blanket]),
+        ] {
+            let headers: Vec<String> = implementations
+                .iter()
+                .filter(|implementation| kinds.contains(&implementation.kind))
+                .map(|implementation| format!("`{}`", implementation.header))
+                .collect();
+            list_section(heading, &headers, page);
+        }
+    }
+
+    /// Renders items with their signature and docs under a heading
+    fn item_section(&self, heading: &str, items: &[&Item], from: &[String], page: &mut String) {
+        if items.is_empty() {
+            return;
+        }
+
+        page.push_str(&format!("\n## {heading}\n"));
+        for item in items {
+            page.push_str(&self.render_item(item, from, 3));
+        }
+    }
+
+    /// Renders variants with their docs and fields
+    fn variant_section(&self, variants: &[&Item], from: &[String], page: &mut String) {
+        if variants.is_empty() {
+            return;
+        }
+
+        page.push_str("\n## Variants\n");
+        for variant in variants {
+            page.push_str(&self.render_item(variant, from, 3));
+
+            let fields = match &variant.inner {
+                ItemEnum::Variant(v) => match &v.kind {
+                    VariantKind::Plain => continue,
+                    VariantKind::Tuple(fields) => self.items(fields.iter().flatten()),
+                    VariantKind::Struct { fields, .. } => self.items(fields),
+                },
+                _ => continue,
+            };
+            page.push('\n');
+            for field in fields {
+                let signature = field.signature().unwrap_or_default();
+                match self.summary(field, from) {
+                    Some(summary) => page.push_str(&format!("- `{signature}`: {summary}\n")),
+                    None => page.push_str(&format!("- `{signature}`\n")),
+                }
+            }
+        }
+    }
+
+    /// Renders an item with its signature as heading of `level`, followed by its docs
+    fn render_item(&self, item: &Item, from: &[String], level: usize) -> String {
+        let signature = item.signature().unwrap_or_else(|| item.name.clone());
+        let mut rendered = format!("\n{} `{signature}`\n", "#".repeat(level));
+        if let Some(deprecation) = deprecation_note(item) {
+            rendered.push_str(&format!("\n{deprecation}\n"));
+        }
+        if let Some(docs) = self.docs(item, from, level) {
+            rendered.push_str(&format!("\n{docs}\n"));
+        }
+        rendered
+    }
+
+    /// The items with the given ids that are part of the index
+    fn items<'i>(&self, ids: impl IntoIterator<Item = &'i rustdoc_types::Id>) -> Vec<&'a Item> {
+        ids.into_iter()
+            .filter_map(|id| self.doc.0.items.get(&id.0.to_string()))
+            .collect()
+    }
+
+    /// The docs of an item with links rewritten and headings nested below `level`
+    fn docs(&self, item: &Item, from: &[String], level: usize) -> Option<String> {
+        let docs = item.docs.as_deref()?;
+        let links: HashMap<&str, String> = self
+            .doc
+            .links_of(item)
+            .into_iter()
+            .filter_map(|(text, target)| Some((text, self.link(target, from)?)))
+            .collect();
+
+        Some(rewrite_links(&nest_headings(docs, level), &links))
+    }
+
+    /// The first paragraph of the docs on one line, with links rewritten
+    fn summary(&self, item: &Item, from: &[String]) -> Option<String> {
+        let docs = self.docs(item, from, 0)?;
+        let summary = docs
+            .lines()
+            .take_while(|line| !line.trim().is_empty())
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join(" ");
+        (!summary.is_empty()).then_some(summary)
+    }
+
+    /// The relative link from the page `from` to the page of `target`, or the
+    /// page it is rendered on
+    fn link(&self, target: &Item, from: &[String]) -> Option<String> {
+        let file = self.page_of(target.canonical_path.as_deref()?)?;
+        Some(relative_path(from, file))
+    }
+
+    /// The file of the page of the item at `path`, or of the page it is part of
+    fn page_of(&self, path: &[String]) -> Option<&Vec<String>> {
+        (1..=path.len())
+            .rev()
+            .find_map(|len| self.layout.files.get(&path[..len]))
+    }
+}
+
+/// The file of an item's page relative to the export directory, `None` for
+/// items rendered as part of another page
+fn page_file(item: &Item, format: ExportFormat) -> Option<Vec<String>> {
+    let path = item.canonical_path.as_deref()?;
+    let extension = format.extension();
+    if is_reexport(item) {
+        return None;
+    }
+
+    let prefix = match item.kind? {
+        ItemKind::Module => {
+            let mut file = path.to_vec();
+            file.push(format!("index.{extension}"));
+            return Some(file);
+        }
+        ItemKind::Struct => "struct",
+        ItemKind::Enum => "enum",
+        ItemKind::Union => "union",
+        ItemKind::Trait => "trait",
+        _ => return None,
+    };
+    let (name, module) = path.split_last()?;
+    let mut file = module.to_vec();
+    file.push(format!("{prefix}.{name}.{extension}"));
+    Some(file)
+}
+
+/// What the page of an item is titled, e.g. `Struct` in ``# Struct `tokio::sync::Mutex` ``
+fn page_title(item: &Item, path: &[String]) -> &'static str {
+    match item.kind {
+        Some(ItemKind::Module) if path.len() == 1 => "Crate",
+        Some(ItemKind::Module) => "Module",
+        Some(ItemKind::Struct) => "Struct",
+        Some(ItemKind::Enum) => "Enum",
+        Some(ItemKind::Union) => "Union",
+        Some(ItemKind::Trait) => "Trait",
+        Some(ItemKind::TraitAlias) => "Trait Alias",
+        Some(ItemKind::Function) => "Function",
+        Some(ItemKind::TypeAlias) => "Type Alias",
+        Some(ItemKind::Constant) => "Constant",
+        Some(ItemKind::Static) => "Static",
+        Some(ItemKind::Macro) => "Macro",
+        Some(ItemKind::Variant) => "Variant",
+        Some(ItemKind::StructField) => "Field",
+        Some(ItemKind::AssocConst) => "Associated Constant",
+        Some(ItemKind::AssocType) => "Associated Type",
+        Some(ItemKind::Primitive) => "Primitive Type",
+        _ => "Item",
+    }
+}
+
+/// The deprecation notice of an item, e.g. ``**Deprecated** since 0.1.0: use `new_api` instead``
+fn deprecation_note(item: &Item) -> Option<String> {
+    let deprecation = item.deprecation.as_ref()?;
+    let mut note = "**Deprecated**".to_string();
+    if let Some(since) = &deprecation.since {
+        note.push_str(&format!(" since {since}"));
+    }
+    if let Some(text) = &deprecation.note {
+        note.push_str(&format!(": {text}"));
+    }
+    Some(note)
+}
+
+fn is_reexport(item: &Item) -> bool {
+    matches!(item.inner, ItemEnum::Use(_))
+}
+
+/// Whether implementors of the trait have to define the item
+fn is_required(item: &Item) -> bool {
+    match &item.inner {
+        ItemEnum::Function(function) => !function.has_body,
+        ItemEnum::AssocType { type_, .. } => type_.is_none(),
+        ItemEnum::AssocConst { value, .. } => value.is_none(),
+        _ => false,
+    }
+}
+
+fn list_section(heading: &str, entries: &[String], page: &mut String) {
+    if entries.is_empty() {
+        return;
+    }
+
+    page.push_str(&format!("\n## {heading}\n\n"));
+    for entry in entries {
+        page.push_str(&format!("- {entry}\n"));
+    }
+}
+
+/// The path of `to` relative to the directory of `from`, both relative to the export directory
+fn relative_path(from: &[String], to: &[String]) -> String {
+    let from_dir = &from[..from.len().saturating_sub(1)];
+    let common = from_dir.iter().zip(to).take_while(|(a, b)| a == b).count();
+
+    let mut path = vec![".."; from_dir.len() - common];
+    path.extend(to[common..].iter().map(String::as_str));
+    path.join("/")
+}
+
+/// Moves the headings of the docs below a heading of `level` and drops the hidden
+/// lines (`# ...`) of Rust code blocks, leaving the rest of the Markdown as is.
+fn nest_headings(docs: &str, level: usize) -> String {
+    let mut lines = Vec::new();
+    let mut fence: Option<bool> = None;
+    for line in docs.lines() {
+        let trimmed = line.trim_start();
+        if let Some(info) = trimmed.strip_prefix("```") {
+            fence = match fence {
+                Some(_) => None,
+                None => Some(is_rust_fence(info)),
+            };
+            // rustdoc treats unlabeled code blocks as Rust
+            if fence == Some(true) {
+                lines.push("```rust".to_string());
+                continue;
+            }
+        } else if fence == Some(true) && (trimmed == "#" || trimmed.starts_with("# ")) {
+            continue;
+        } else if fence.is_none() && trimmed.starts_with('#') {
+            let hashes = trimmed.chars().take_while(|&c| c == '#').count();
+            if trimmed[hashes..].starts_with(' ') {
+                let nested = "#".repeat((hashes + level).min(6));
+                lines.push(format!("{nested}{}", &trimmed[hashes..]));
+                continue;
+            }
+        }
+        lines.push(line.to_string());
+    }
+    lines.join("\n")
+}
+
+/// Rewrites intra-doc links to the given destinations
+///
+/// Handles inline links (`[text](Config::new)`), reference links (`[text][Config::new]`),
+/// shortcut links (`` [`Config::new`] ``) and the definitions of reference links
+/// (`` [`Config`]: crate::Config ``). Code spans and code blocks are left as they are.
+fn rewrite_links(docs: &str, links: &HashMap<&str, String>) -> String {
+    let mut rewritten = String::with_capacity(docs.len());
+    let mut fence: Option<&str> = None;
+    for line in docs.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(marker) if trimmed.starts_with(marker) => fence = None,
+            Some(_) => {}
+            None if trimmed.starts_with("```") || trimmed.starts_with("~~~") => {
+                fence = Some(&trimmed[..3]);
+            }
+            None if is_link_definition(trimmed) => {
+                rewritten.push_str(&rewrite_definition(line, links));
+                continue;
+            }
+            None => {
+                rewrite_inline(line, links, &mut rewritten);
+                continue;
+            }
+        }
+        rewritten.push_str(line);
+    }
+    rewritten
+}
+
+/// Rewrites the destination of a reference link definition like `[text]: Config::new`
+fn rewrite_definition(line: &str, links: &HashMap<&str, String>) -> String {
+    let Some(label_end) = line.find("]:") else {
+        return line.to_string();
+    };
+    let target = line[label_end + 2..].trim_start();
+    let target_end = target.find(char::is_whitespace).unwrap_or(target.len());
+    let start = line.len() - target.len();
+    match links.get(&target[..target_end]) {
+        Some(destination) => format!(
+            "{}{destination}{}",
+            &line[..start],
+            &line[start + target_end..]
+        ),
+        None => line.to_string(),
+    }
+}
+
+/// Rewrites the inline, reference and shortcut links of a line, skipping code spans
+fn rewrite_inline(line: &str, links: &HashMap<&str, String>, out: &mut String) {
+    let mut rest = line;
+    while let Some(start) = rest.find(['[', '`', '\\']) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let skip = match rest.as_bytes()[0] {
+            b'`' => Some(code_span_len(rest)),
+            b'\\' => Some(rest[1..].chars().next().map_or(1, |c| 1 + c.len_utf8())),
+            _ => None,
+        };
+        if let Some(skip) = skip {
+            out.push_str(&rest[..skip]);
+            rest = &rest[skip..];
+            continue;
+        }
+
+        let Some(text_end) = link_text_end(rest) else {
+            out.push('[');
+            rest = &rest[1..];
+            continue;
+        };
+        let text = &rest[1..text_end];
+        let after = &rest[text_end + 1..];
+        out.push_str(&rest[..=text_end]);
+
+        let delimited = |open, close| {
+            let inner = after.strip_prefix(open)?;
+            inner
+                .find(close)
+                .map(|end| (&inner[..end], &inner[end + 1..]))
+        };
+        if let Some((target, after)) = delimited('(', ')') {
+            let destination = links.get(target).map_or(target, String::as_str);
+            out.push_str(&format!("({destination})"));
+            rest = after;
+        } else if let Some((reference, after)) = delimited('[', ']') {
+            // `[text][]` refers to `text`
+            let key = if reference.is_empty() {
+                text
+            } else {
+                reference
+            };
+            match links.get(key) {
+                Some(destination) => out.push_str(&format!("({destination})")),
+                None => out.push_str(&format!("[{reference}]")),
+            }
+            rest = after;
+        } else {
+            if let Some(destination) = links.get(text) {
+                out.push_str(&format!("({destination})"));
+            }
+            rest = after;
+        }
+    }
+    out.push_str(rest);
+}
+
+/// The length of the code span at the start of `text`, only its opening backticks
+/// if it isn't closed
+fn code_span_len(text: &str) -> usize {
+    let ticks = text.len() - text.trim_start_matches('`').len();
+    let mut offset = ticks;
+    while let Some(start) = text[offset..].find('`') {
+        let start = offset + start;
+        let run = text[start..].len() - text[start..].trim_start_matches('`').len();
+        if run == ticks {
+            return start + run;
+        }
+        offset = start + run;
+    }
+    ticks
+}
+
+/// The position of the `]` closing the link text `text` starts with
+fn link_text_end(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut position = 1;
+    while position < text.len() {
+        match text.as_bytes()[position] {
+            b'`' => {
+                position += code_span_len(&text[position..]);
+                continue;
+            }
+            b'\\' => position += 1,
+            b'[' => depth += 1,
+            b']' if depth == 0 => return Some(position),
+            b']' => depth -= 1,
+            _ => {}
+        }
+        position += 1;
+    }
+    None
+}
+
+/// Converts a rendered page to plain text
+///
+/// Headings are underlined, code blocks are indented, inline code loses its
+/// backticks and links are written as `text (destination)`.
+fn to_plain_text(markdown: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            lines.push(format!("    {line}"));
+            continue;
+        }
+
+        let hashes = line.chars().take_while(|&c| c == '#').count();
+        if hashes > 0 && line[hashes..].starts_with(' ') {
+            let heading = plain_inline(line[hashes..].trim());
+            let underline = match hashes {
+                1 => "=".repeat(heading.chars().count()),
+                2 => "-".repeat(heading.chars().count()),
+                _ => String::new(),
+            };
+            lines.push(heading);
+            if !underline.is_empty() {
+                lines.push(underline);
+            }
+        } else {
+            lines.push(plain_inline(line));
+        }
+    }
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+/// Strips the inline Markdown of a line
+fn plain_inline(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find('[') {
+        let link = rest[start + 1..].find("](").and_then(|close| {
+            let text_end = start + 1 + close;
+            let destination_end = text_end + 2 + rest[text_end + 2..].find(')')?;
+            Some((text_end, destination_end))
+        });
+        match link {
+            Some((text_end, destination_end)) => {
+                out.push_str(&rest[..start]);
+                out.push_str(&rest[start + 1..text_end]);
+                out.push_str(&format!(" ({})", &rest[text_end + 2..destination_end]));
+                rest = &rest[destination_end + 1..];
+            }
+            None => {
+                out.push_str(&rest[..=start]);
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out.replace("**", "").replace('`', "")
+}
@@ -0,0 +1,22 @@
+use crate::{Item, MethodOrigin};
+
+/// A single search result with the details of how it matched.
+///
+/// Returned by [`Doc::search_detailed`](crate::Doc::search_detailed), this keeps the
+/// information that [`Doc::search`](crate::Doc::search) throws away, so frontends can
+/// show a confidence or highlight the matched characters.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SearchHit<'a> {
+    /// The matched item
+    pub item: &'a Item,
+    /// The score given by the ranker, higher is better
+    pub score: i64,
+    /// The key that matched the query (e.g. `std::vec::Vec::push`)
+    pub matched_key: &'a str,
+    /// Char indices into `matched_key` of the characters that matched the query
+    pub match_indices: Vec<usize>,
+    /// How the method is reachable on the type in `matched_key`, e.g. through `Deref`.
+    /// `None` if `matched_key` is a path of the item itself
+    pub origin: Option<&'a MethodOrigin>,
+}
@@ -0,0 +1,208 @@
+use super::{Doc, Indexed};
+use crate::{
+    Item,
+    doc::signature::{impl_header, type_to_string},
+};
+use rustdoc_types::{Impl, ItemEnum, Type};
+
+/// Auto traits of the standard library, which are usually implemented by the compiler.
+const AUTO_TRAITS: [&str; 7] = [
+    "Send",
+    "Sync",
+    "Unpin",
+    "UnwindSafe",
+    "RefUnwindSafe",
+    "Freeze",
+    "UnsafeUnpin",
+];
+
+/// The kind of an impl block.
+///
+/// The variants are ordered the way rustdoc lists implementations on a type's page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ImplKind {
+    /// `impl Type { .. }`
+    Inherent,
+    /// `impl Trait for Type { .. }`
+    Trait,
+    /// A handwritten impl of an auto trait, e.g. `unsafe impl Send for Type {}`
+    Auto,
+    /// An auto trait impl generated by the compiler, e.g. `Send` for a type whose fields are all `Send`
+    Synthetic,
+    /// An impl for every type satisfying some bounds, e.g. `impl<T: Display> ToString for T`
+    Blanket,
+}
+
+/// An impl block relating a type to a trait (or to itself for inherent impls).
+///
+/// Returned by [`Doc::implementors_of`] and [`Doc::traits_implemented_by`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Implementation<'a> {
+    /// The id of the impl block
+    pub id: String,
+    /// The kind of impl
+    pub kind: ImplKind,
+    /// Fully qualified path of the implemented trait, `None` for inherent impls
+    pub trait_path: Option<Vec<String>>,
+    /// The type the impl is for, as written in the source (e.g. `Wrapper` or `Vec<T>`)
+    pub for_type: String,
+    /// The impl header, e.g. `impl<T: Display> Greet for T`
+    pub header: String,
+    /// The indexed items (methods, associated types and constants) of the impl block
+    pub items: Vec<&'a Item>,
+    /// The impl block as given by rustdoc
+    pub inner: &'a Impl,
+}
+
+impl Doc<Indexed> {
+    /// Lists the impl blocks implementing a trait
+    ///
+    /// Only impls within the documented crate are known, implementations of
+    /// the trait in downstream crates are not part of its documentation.
+    ///
+    /// # Arguments
+    ///
+    /// - `trait_item` - The trait, usually found with [`Doc::search`]
+    ///
+    /// # Returns
+    ///
+    /// The implementations sorted by kind, then by type. Empty if `trait_item`
+    /// is not a trait.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// let doc = Doc::from_json("path/to/docs.json")?.parse()?.build_search_index();
+    /// let serialize = doc.search("serde::Serialize", Some(1)).unwrap()[0];
+    /// for implementation in doc.implementors_of(serialize) {
+    ///     println!("{}", implementation.header);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn implementors_of(&self, trait_item: &Item) -> Vec<Implementation<'_>> {
+        let ItemEnum::Trait(t) = &trait_item.inner else {
+            return Vec::new();
+        };
+
+        self.implementations(t.implementations.iter().map(|id| id.0.to_string()))
+    }
+
+    /// Lists the impl blocks of a type, including inherent impls
+    ///
+    /// Besides the handwritten impls this includes the auto trait impls generated
+    /// by the compiler and blanket impls that apply to the type.
+    ///
+    /// # Arguments
+    ///
+    /// - `type_item` - A struct, enum, union or primitive, usually found with [`Doc::search`]
+    ///
+    /// # Returns
+    ///
+    /// The implementations sorted by kind, then by trait. Empty if `type_item`
+    /// is not a type with impls.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::{Doc, ImplKind};
+    /// let doc = Doc::from_json("path/to/docs.json")?.parse()?.build_search_index();
+    /// let client = doc.search("reqwest::Client", Some(1)).unwrap()[0];
+    /// let traits = doc.traits_implemented_by(client);
+    /// let is_send = traits
+    ///     .iter()
+    ///     .filter(|i| matches!(i.kind, ImplKind::Auto | ImplKind::Synthetic))
+    ///     .any(|i| i.trait_path.as_ref().is_some_and(|p| p.ends_with(&["Send".to_string()])));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn traits_implemented_by(&self, type_item: &Item) -> Vec<Implementation<'_>> {
+        let impls = match &type_item.inner {
+            ItemEnum::Struct(s) => &s.impls,
+            ItemEnum::Enum(e) => &e.impls,
+            ItemEnum::Union(u) => &u.impls,
+            ItemEnum::Primitive(p) => &p.impls,
+            _ => return Vec::new(),
+        };
+
+        self.implementations(impls.iter().map(|id| id.0.to_string()))
+    }
+
+    fn implementations(&self, ids: impl Iterator<Item = String>) -> Vec<Implementation<'_>> {
+        let mut implementations: Vec<Implementation> = ids
+            .filter_map(|id| {
+                let (id, impl_block) = self.0.impls.get_key_value(&id)?;
+                Some(self.implementation(id, impl_block))
+            })
+            .collect();
+
+        implementations.sort_by(|a, b| {
+            a.kind
+                .cmp(&b.kind)
+                .then_with(|| a.trait_path.cmp(&b.trait_path))
+                .then_with(|| a.for_type.cmp(&b.for_type))
+        });
+        implementations
+    }
+
+    fn implementation<'a>(&'a self, id: &str, impl_block: &'a Impl) -> Implementation<'a> {
+        let trait_path = impl_block.trait_.as_ref().map(|trait_| {
+            self.0
+                .paths
+                .get(&trait_.id.0.to_string())
+                .cloned()
+                .unwrap_or_else(|| trait_.path.split("::").map(String::from).collect())
+        });
+
+        let kind = if is_blanket_impl(impl_block) {
+            ImplKind::Blanket
+        } else if impl_block.is_synthetic {
+            ImplKind::Synthetic
+        } else if trait_path.as_deref().is_some_and(is_auto_trait) {
+            ImplKind::Auto
+        } else if trait_path.is_some() {
+            ImplKind::Trait
+        } else {
+            ImplKind::Inherent
+        };
+
+        let items = impl_block
+            .items
+            .iter()
+            .filter_map(|id| self.0.items.get(&id.0.to_string()))
+            .collect();
+
+        Implementation {
+            id: id.to_string(),
+            kind,
+            trait_path,
+            for_type: type_to_string(&impl_block.for_),
+            header: impl_header(impl_block),
+            items,
+            inner: impl_block,
+        }
+    }
+}
+
+/// Whether an impl is a blanket impl, either its declaration or one of its copies.
+///
+/// The declaration of a blanket impl is for a plain generic, the copies rustdoc
+/// attaches to every type it applies to have `blanket_impl` set.
+pub(crate) fn is_blanket_impl(impl_block: &Impl) -> bool {
+    impl_block.blanket_impl.is_some() || matches!(impl_block.for_, Type::Generic(_))
+}
+
+fn is_auto_trait(path: &[String]) -> bool {
+    matches!(
+        path.first().map(String::as_str),
+        Some("core" | "std" | "alloc")
+    ) && path
+        .last()
+        .is_some_and(|name| AUTO_TRAITS.contains(&name.as_str()))
+}
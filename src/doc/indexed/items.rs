@@ -0,0 +1,225 @@
+use super::{Doc, Indexed, SearchKey};
+use crate::Item;
+use rustdoc_types::{Id, ItemKind};
+use std::collections::{HashMap, HashSet};
+
+/// The ids of the named items, sorted by path and then by id
+pub(super) fn sorted_ids(items: &HashMap<String, Item>) -> Vec<String> {
+    let mut sorted: Vec<&Item> = items
+        .values()
+        .filter(|item| !item.name.is_empty())
+        .collect();
+    sorted.sort_by_cached_key(|item| order(item));
+    sorted.into_iter().map(|item| item.id.clone()).collect()
+}
+
+/// The positions of the search keys by their path, for exact lookups
+pub(super) fn exact_keys(search_index: &[SearchKey]) -> HashMap<String, Vec<usize>> {
+    let mut exact: HashMap<String, Vec<usize>> = HashMap::new();
+    for (position, search_key) in search_index.iter().enumerate() {
+        exact
+            .entry(search_key.key.clone())
+            .or_default()
+            .push(position);
+    }
+    exact
+}
+
+/// Keys for the canonical paths that aren't keyed yet
+///
+/// The members of a re-exported type are only keyed under the definition path of
+/// the type, e.g. `my_crate::inner::Reexported::noop` but not `my_crate::Reexported::noop`.
+pub(super) fn canonical_keys(
+    search_index: &[SearchKey],
+    items: &HashMap<String, Item>,
+) -> Vec<SearchKey> {
+    let keyed: HashSet<(&str, &str)> = search_index
+        .iter()
+        .map(|search_key| (search_key.id.as_str(), search_key.key.as_str()))
+        .collect();
+
+    let mut keys = Vec::new();
+    for item in items.values().filter(|item| !item.name.is_empty()) {
+        if let Some(canonical_path) = &item.canonical_path {
+            let key = canonical_path.join("::");
+            if !keyed.contains(&(item.id.as_str(), key.as_str())) {
+                keys.push(SearchKey::new(item.id.clone(), key));
+            }
+        }
+    }
+    keys
+}
+
+/// The name of the crate's root module, which `crate::` paths start at
+pub(super) fn crate_name(items: &HashMap<String, Item>) -> Option<String> {
+    items
+        .values()
+        .find(|item| {
+            item.crate_id == 0 && item.kind == Some(ItemKind::Module) && item.path.len() == 1
+        })
+        .map(|item| item.name.clone())
+}
+
+/// The order of [`Doc::items`]
+fn order(item: &Item) -> (Vec<String>, Option<u32>) {
+    (item.path.clone(), item.id.parse().ok())
+}
+
+/// Brings a path into the form of the search keys
+///
+/// A leading `crate` or `self` is replaced by the name of the crate, generic
+/// arguments are dropped with or without turbofish (`Vec<T>::push` and
+/// `Vec::<T>::push` become `Vec::push`) and so are a leading `::` and whitespace.
+fn normalize(path: &str, crate_name: Option<&str>) -> String {
+    let mut plain = String::with_capacity(path.len());
+    let mut depth = 0usize;
+    for c in path.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            c if depth == 0 && !c.is_whitespace() => plain.push(c),
+            _ => {}
+        }
+    }
+
+    let mut segments: Vec<&str> = plain
+        .split("::")
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    if let (Some(first), Some(crate_name)) = (segments.first_mut(), crate_name)
+        && matches!(*first, "crate" | "self")
+    {
+        *first = crate_name;
+    }
+    segments.join("::")
+}
+
+impl Doc<Indexed> {
+    /// Iterates over every indexed item of the crate
+    ///
+    /// This includes the items of other crates the crate re-exports, methods and
+    /// fields. Unlike [`Doc::search`] this needs no query, so it suits tools walking
+    /// the whole crate.
+    ///
+    /// # Returns
+    ///
+    /// The items sorted by path, items at the same path by id, so the order is the
+    /// same on every call and every run.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// let doc = Doc::from_json("path/to/docs.json")?.parse()?.build_search_index();
+    /// let deprecated = doc.items().filter(|item| item.deprecation.is_some()).count();
+    /// println!("{deprecated} deprecated items");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn items(&self) -> impl Iterator<Item = &Item> {
+        self.0.sorted.iter().filter_map(|id| self.0.items.get(id))
+    }
+
+    /// Iterates over the items of one kind, in the order of [`Doc::items`]
+    ///
+    /// # Arguments
+    ///
+    /// - `kind` - The kind of the items, e.g. `ItemKind::Trait`
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// use rustdoc_types::ItemKind;
+    /// let doc = Doc::from_json("path/to/docs.json")?.parse()?.build_search_index();
+    /// for item in doc.iter_by_kind(ItemKind::Trait) {
+    ///     println!("{}", item.path.join("::"));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_by_kind(&self, kind: ItemKind) -> impl Iterator<Item = &Item> {
+        self.items().filter(move |item| item.kind == Some(kind))
+    }
+
+    /// Finds every item reachable at an exact path
+    ///
+    /// Unlike [`Doc::search`] the path is compared case-sensitively and as a whole,
+    /// in constant time. The definition path, re-exports and the paths of methods
+    /// on types (including trait and `Deref` methods) are all reachable paths.
+    /// `crate::` and `self::` stand for the crate's root and generic arguments are
+    /// ignored, so `crate::Wrapper<T>::len` finds the same items as `my_crate::Wrapper::len`.
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The path, e.g. `tokio::sync::Mutex`
+    ///
+    /// # Returns
+    ///
+    /// The items at the path in the order of [`Doc::items`], e.g. a function and a
+    /// macro of the same name. Empty if nothing is reachable at the path.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// let doc = Doc::from_json("path/to/docs.json")?.parse()?.build_search_index();
+    /// for item in doc.lookup("std::vec::Vec<T>::push") {
+    ///     println!("{:?} {}", item.kind, item.path.join("::"));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lookup(&self, path: &str) -> Vec<&Item> {
+        let mut items: Vec<&Item> = self
+            .exact_keys(path)
+            .filter_map(|search_key| self.0.items.get(&search_key.id))
+            .collect();
+        items.sort_by_cached_key(|item| order(item));
+        items.dedup_by(|a, b| a.id == b.id);
+        items
+    }
+
+    /// Finds an item by its exact path, e.g. `tokio::sync::Mutex`
+    ///
+    /// # Returns
+    ///
+    /// The first item [`Doc::lookup`] finds at the path, `None` if no item is
+    /// reachable at the path.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// let doc = Doc::from_json("path/to/docs.json")?.parse()?.build_search_index();
+    /// let mutex = doc.get_by_path("tokio::sync::Mutex").expect("tokio has a mutex");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_by_path(&self, path: &str) -> Option<&Item> {
+        self.lookup(path).into_iter().next()
+    }
+
+    /// The search keys of exactly `path`, after [normalizing](normalize) it
+    pub(super) fn exact_keys(&self, path: &str) -> impl Iterator<Item = &SearchKey> {
+        let path = normalize(path, self.0.crate_name.as_deref());
+        self.0
+            .exact
+            .get(&path)
+            .into_iter()
+            .flatten()
+            .map(|&position| &self.0.search_index[position])
+    }
+
+    /// Returns the item with the given rustdoc id
+    ///
+    /// Ids are the ones of the crate's rustdoc JSON, as found in the AST of
+    /// [`Parsed`](crate::Parsed) or, as a string, in [`Item::id`].
+    pub fn get_by_id(&self, id: Id) -> Option<&Item> {
+        self.0.items.get(&id.0.to_string())
+    }
+}
@@ -0,0 +1,251 @@
+use super::{Doc, ImplKind, Indexed, SearchKey};
+use crate::Item;
+use rustdoc_types::{ItemEnum, ItemKind, Type};
+use std::collections::HashSet;
+
+/// Deref chains longer than this are cut off, real types rarely go beyond three levels
+const MAX_DEREF_DEPTH: usize = 8;
+
+/// How a method is reachable on a type.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MethodOrigin {
+    /// Defined in an inherent impl of the type
+    Inherent,
+    /// Defined in or provided by a trait the type implements
+    Trait {
+        /// Fully qualified path of the trait
+        trait_path: Vec<String>,
+    },
+    /// Defined in a blanket impl applying to the type, e.g. `to_string` from
+    /// `impl<T: Display> ToString for T`
+    Blanket {
+        /// Fully qualified path of the trait
+        trait_path: Vec<String>,
+    },
+    /// An inherent method of a type the type derefs to, e.g. slice methods on `Vec`
+    Deref {
+        /// Path of the type the method is defined on
+        target: Vec<String>,
+    },
+}
+
+/// A method callable on a type, returned by [`Doc::methods_of`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Method<'a> {
+    /// The method
+    pub item: &'a Item,
+    /// How the method is reachable on the type
+    pub origin: MethodOrigin,
+}
+
+impl Doc<Indexed> {
+    /// Lists all methods callable on a type
+    ///
+    /// Like the method sections on a type's page on docs.rs this includes the
+    /// methods of inherent impls, of trait impls within the crate (with the provided
+    /// methods the impl doesn't override), of blanket impls applying to the type and
+    /// the inherent methods of the types it derefs to ("Methods from Deref").
+    /// Methods reachable through `Deref` are left out if the type itself has a
+    /// method of the same name.
+    ///
+    /// # Arguments
+    ///
+    /// - `type_item` - A struct, enum, union or primitive, usually found with [`Doc::search`]
+    ///
+    /// # Returns
+    ///
+    /// The methods in the order inherent, trait, blanket and deref methods. Empty if
+    /// `type_item` is not a type with impls.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// let doc = Doc::from_json("path/to/alloc.json")?.parse()?.build_search_index();
+    /// let vec = doc.search("alloc::vec::Vec", Some(1)).unwrap()[0];
+    /// for method in doc.methods_of(vec) {
+    ///     println!("{} ({:?})", method.item.name, method.origin);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn methods_of(&self, type_item: &Item) -> Vec<Method<'_>> {
+        let mut methods = Vec::new();
+        let mut visited = HashSet::new();
+        self.collect_methods(type_item, None, 0, &mut methods, &mut visited);
+        methods
+    }
+
+    fn collect_methods<'a>(
+        &'a self,
+        type_item: &Item,
+        deref_target: Option<&[String]>,
+        depth: usize,
+        methods: &mut Vec<Method<'a>>,
+        visited: &mut HashSet<String>,
+    ) {
+        if depth > MAX_DEREF_DEPTH || !visited.insert(type_item.id.clone()) {
+            return;
+        }
+
+        let mut targets = Vec::new();
+        for implementation in self.traits_implemented_by(type_item) {
+            match (implementation.kind, &implementation.trait_path) {
+                (ImplKind::Inherent, _) => {
+                    let origin = match deref_target {
+                        Some(target) => MethodOrigin::Deref {
+                            target: target.to_vec(),
+                        },
+                        None => MethodOrigin::Inherent,
+                    };
+                    for item in implementation.items.iter().filter(|item| is_method(item)) {
+                        // a method of the same name on the type shadows the deref'd one
+                        let shadowed = deref_target.is_some()
+                            && methods.iter().any(|method| method.item.name == item.name);
+                        if !shadowed {
+                            methods.push(Method {
+                                item,
+                                origin: origin.clone(),
+                            });
+                        }
+                    }
+                }
+                // Only the target's inherent methods are listed, like rustdoc does
+                (kind @ (ImplKind::Trait | ImplKind::Blanket), Some(trait_path))
+                    if deref_target.is_none() =>
+                {
+                    if kind == ImplKind::Trait && is_deref(trait_path) {
+                        targets.extend(
+                            implementation
+                                .items
+                                .iter()
+                                .filter_map(|item| self.deref_target(item)),
+                        );
+                    }
+
+                    let trait_path = trait_path.clone();
+                    let origin = if kind == ImplKind::Blanket {
+                        MethodOrigin::Blanket { trait_path }
+                    } else {
+                        MethodOrigin::Trait { trait_path }
+                    };
+                    let provided = self.provided_methods(implementation.inner);
+                    for item in implementation
+                        .items
+                        .iter()
+                        .copied()
+                        .chain(provided)
+                        .filter(|item| is_method(item))
+                    {
+                        methods.push(Method {
+                            item,
+                            origin: origin.clone(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for target in targets {
+            self.collect_methods(target, Some(&target.path), depth + 1, methods, visited);
+        }
+    }
+
+    /// The trait methods an impl block doesn't override
+    fn provided_methods(&self, impl_block: &rustdoc_types::Impl) -> Vec<&Item> {
+        let Some(ItemEnum::Trait(t)) = impl_block
+            .trait_
+            .as_ref()
+            .and_then(|trait_| self.0.items.get(&trait_.id.0.to_string()))
+            .map(|item| &item.inner)
+        else {
+            return Vec::new();
+        };
+
+        t.items
+            .iter()
+            .filter_map(|id| self.0.items.get(&id.0.to_string()))
+            .filter(|item| impl_block.provided_trait_methods.contains(&item.name))
+            .collect()
+    }
+
+    /// Resolves the `type Target` of a `Deref` impl to the target type's item
+    fn deref_target(&self, assoc_item: &Item) -> Option<&Item> {
+        match &assoc_item.inner {
+            ItemEnum::AssocType {
+                type_: Some(ty), ..
+            } if assoc_item.name == "Target" => self.type_item(ty),
+            _ => None,
+        }
+    }
+
+    /// Finds the item of a type, only types documented in this crate have one
+    fn type_item(&self, ty: &Type) -> Option<&Item> {
+        let primitive = match ty {
+            Type::ResolvedPath(path) => return self.0.items.get(&path.id.0.to_string()),
+            Type::Primitive(name) => name.as_str(),
+            Type::Slice(_) => "slice",
+            Type::Array { .. } => "array",
+            _ => return None,
+        };
+
+        self.0
+            .items
+            .values()
+            .find(|item| matches!(&item.inner, ItemEnum::Primitive(p) if p.name == primitive))
+    }
+
+    /// Search keys for the methods reachable on each type through traits and `Deref`.
+    ///
+    /// The keys are put under the type's path, e.g. `fixture::Wrapper::len`, so the
+    /// methods can be found the way they are called. Paths that already exist are skipped.
+    pub(super) fn method_keys(&self) -> Vec<SearchKey> {
+        let existing: HashSet<&str> = self
+            .0
+            .search_index
+            .iter()
+            .map(|key| key.key.as_str())
+            .collect();
+
+        let mut added = HashSet::new();
+        let mut keys = Vec::new();
+        for type_item in self.0.items.values().filter(|item| {
+            item.crate_id == 0
+                && matches!(
+                    item.kind,
+                    Some(ItemKind::Struct | ItemKind::Enum | ItemKind::Union | ItemKind::Primitive)
+                )
+        }) {
+            let type_path = type_item.path.join("::");
+            for method in self.methods_of(type_item) {
+                if method.origin == MethodOrigin::Inherent {
+                    continue;
+                }
+
+                let key = format!("{type_path}::{}", method.item.name);
+                if !existing.contains(key.as_str()) && added.insert(key.clone()) {
+                    keys.push(
+                        SearchKey::new(method.item.id.clone(), key).with_origin(method.origin),
+                    );
+                }
+            }
+        }
+        keys
+    }
+}
+
+fn is_method(item: &Item) -> bool {
+    matches!(item.inner, ItemEnum::Function(_))
+}
+
+fn is_deref(trait_path: &[String]) -> bool {
+    matches!(
+        trait_path.first().map(String::as_str),
+        Some("core" | "std" | "alloc")
+    ) && trait_path.last().is_some_and(|name| name == "Deref")
+}
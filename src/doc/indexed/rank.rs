@@ -0,0 +1,425 @@
+use super::SearchKey;
+use crate::Item;
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
+use rustdoc_types::{Attribute, ItemKind};
+use std::collections::HashMap;
+
+/// A search key that matched the prefilter and is about to be scored.
+///
+/// The item behind the key is only looked up when a [`Ranker`] asks for it,
+/// so rankers that score on the key alone stay cheap.
+pub struct Candidate<'a> {
+    pub(super) search_key: &'a SearchKey,
+    pub(super) items: &'a HashMap<String, Item>,
+}
+
+impl<'a> Candidate<'a> {
+    /// The key as indexed (e.g. `std::vec::Vec::push`)
+    pub fn key(&self) -> &'a str {
+        &self.search_key.key
+    }
+
+    /// The key in lowercase
+    pub fn lower_key(&self) -> &'a str {
+        &self.search_key.lower
+    }
+
+    /// The last path segment of the key in lowercase (e.g. `push`)
+    pub fn lower_name(&self) -> &'a str {
+        let lower = self.lower_key();
+        lower.rsplit("::").next().unwrap_or(lower)
+    }
+
+    /// The item the key points to
+    pub fn item(&self) -> &'a Item {
+        // keys without an item are dropped when the index is built
+        &self.items[&self.search_key.id]
+    }
+}
+
+/// Scores search candidates against a query.
+///
+/// [`Doc::search`](crate::Doc::search) uses [`SkimRanker`]; pass any other ranker to
+/// [`Doc::search_with`](crate::Doc::search_with) to change how results are ordered.
+/// Results are sorted by score descending, ties are broken by the shorter key.
+///
+/// Rankers can be stacked, the boosting and weighting rankers all wrap another one:
+///
+/// ```rust,ignore
+/// use docsrs::{KindWeights, Penalize, PrefixBoost, SkimRanker};
+/// let ranker = Penalize::new(KindWeights::new(PrefixBoost::new(SkimRanker::default())));
+/// let results = doc.search_with("spawn", 10, &ranker);
+/// ```
+pub trait Ranker {
+    /// Scores a candidate, returning `None` if it doesn't match at all.
+    ///
+    /// `query` is already lowercased, compare it against [`Candidate::lower_key`].
+    fn score(&self, query: &str, candidate: &Candidate<'_>) -> Option<i64>;
+
+    /// Returns the char indices of [`Candidate::lower_key`] that matched the query.
+    ///
+    /// Only called for the returned results of
+    /// [`Doc::search_detailed`](crate::Doc::search_detailed). Defaults to the
+    /// skim algorithm, override it when the ranker matches differently.
+    fn indices(&self, query: &str, candidate: &Candidate<'_>) -> Option<Vec<usize>> {
+        SkimMatcherV2::default()
+            .fuzzy_indices(candidate.lower_key(), query)
+            .map(|(_, indices)| indices)
+    }
+
+    /// Whether a match requires every character of the query to occur in the key.
+    ///
+    /// If so, the search skips keys that are missing a character before calling
+    /// [`Ranker::score`]. Return `false` for matchers that treat some characters
+    /// (like whitespace) specially.
+    fn requires_all_chars(&self) -> bool {
+        true
+    }
+}
+
+impl<R: Ranker + ?Sized> Ranker for &R {
+    fn score(&self, query: &str, candidate: &Candidate<'_>) -> Option<i64> {
+        (**self).score(query, candidate)
+    }
+
+    fn indices(&self, query: &str, candidate: &Candidate<'_>) -> Option<Vec<usize>> {
+        (**self).indices(query, candidate)
+    }
+
+    fn requires_all_chars(&self) -> bool {
+        (**self).requires_all_chars()
+    }
+}
+
+impl<R: Ranker + ?Sized> Ranker for Box<R> {
+    fn score(&self, query: &str, candidate: &Candidate<'_>) -> Option<i64> {
+        (**self).score(query, candidate)
+    }
+
+    fn indices(&self, query: &str, candidate: &Candidate<'_>) -> Option<Vec<usize>> {
+        (**self).indices(query, candidate)
+    }
+
+    fn requires_all_chars(&self) -> bool {
+        (**self).requires_all_chars()
+    }
+}
+
+/// Fuzzy matching with the skim V2 algorithm, the default ranker.
+#[derive(Default)]
+pub struct SkimRanker {
+    matcher: SkimMatcherV2,
+}
+
+impl Ranker for SkimRanker {
+    fn score(&self, query: &str, candidate: &Candidate<'_>) -> Option<i64> {
+        self.matcher.fuzzy_match(candidate.lower_key(), query)
+    }
+
+    fn indices(&self, query: &str, candidate: &Candidate<'_>) -> Option<Vec<usize>> {
+        self.matcher
+            .fuzzy_indices(candidate.lower_key(), query)
+            .map(|(_, indices)| indices)
+    }
+}
+
+/// Fuzzy matching with the algorithm used by the helix editor (requires the `nucleo` feature).
+///
+/// Whitespace separates the query into parts which have to match independently,
+/// so `"vec push"` matches `std::vec::Vec::push`.
+#[cfg(feature = "nucleo")]
+pub struct NucleoRanker {
+    state: std::cell::RefCell<NucleoState>,
+}
+
+#[cfg(feature = "nucleo")]
+struct NucleoState {
+    matcher: nucleo_matcher::Matcher,
+    query: String,
+    pattern: nucleo_matcher::pattern::Pattern,
+    buf: Vec<char>,
+}
+
+#[cfg(feature = "nucleo")]
+impl Default for NucleoRanker {
+    fn default() -> Self {
+        Self {
+            state: std::cell::RefCell::new(NucleoState {
+                matcher: nucleo_matcher::Matcher::new(nucleo_matcher::Config::DEFAULT),
+                query: String::new(),
+                pattern: nucleo_matcher::pattern::Pattern::default(),
+                buf: Vec::new(),
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "nucleo")]
+impl Ranker for NucleoRanker {
+    fn score(&self, query: &str, candidate: &Candidate<'_>) -> Option<i64> {
+        use nucleo_matcher::{
+            Utf32Str,
+            pattern::{CaseMatching, Normalization, Pattern},
+        };
+
+        let mut state = self.state.borrow_mut();
+        let NucleoState {
+            matcher,
+            query: parsed,
+            pattern,
+            buf,
+        } = &mut *state;
+
+        // the pattern is parsed once per query, not once per candidate
+        if parsed != query {
+            *pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
+            *parsed = query.to_string();
+        }
+
+        pattern
+            .score(Utf32Str::new(candidate.lower_key(), buf), matcher)
+            .map(i64::from)
+    }
+
+    fn indices(&self, query: &str, candidate: &Candidate<'_>) -> Option<Vec<usize>> {
+        use nucleo_matcher::{
+            Utf32Str,
+            pattern::{CaseMatching, Normalization, Pattern},
+        };
+
+        let mut state = self.state.borrow_mut();
+        let NucleoState { matcher, buf, .. } = &mut *state;
+
+        let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
+        let mut indices = Vec::new();
+        pattern.indices(
+            Utf32Str::new(candidate.lower_key(), buf),
+            matcher,
+            &mut indices,
+        )?;
+
+        // atoms are matched independently, so the indices aren't sorted or unique
+        indices.sort_unstable();
+        indices.dedup();
+        Some(indices.into_iter().map(|i| i as usize).collect())
+    }
+
+    fn requires_all_chars(&self) -> bool {
+        false
+    }
+}
+
+/// Boosts candidates where the key or one of its path segments starts with the query.
+pub struct PrefixBoost<R> {
+    inner: R,
+    bonus: i64,
+}
+
+impl<R: Ranker> PrefixBoost<R> {
+    /// Wraps `inner` with the default bonus.
+    pub fn new(inner: R) -> Self {
+        Self { inner, bonus: 50 }
+    }
+
+    /// Sets the score added to prefix matches.
+    pub fn bonus(mut self, bonus: i64) -> Self {
+        self.bonus = bonus;
+        self
+    }
+}
+
+impl<R: Ranker> Ranker for PrefixBoost<R> {
+    fn score(&self, query: &str, candidate: &Candidate<'_>) -> Option<i64> {
+        let score = self.inner.score(query, candidate)?;
+        let key = candidate.lower_key();
+
+        let is_prefix = !query.is_empty()
+            && (key.starts_with(query)
+                || key.split("::").any(|segment| segment.starts_with(query)));
+
+        Some(if is_prefix { score + self.bonus } else { score })
+    }
+
+    fn indices(&self, query: &str, candidate: &Candidate<'_>) -> Option<Vec<usize>> {
+        self.inner.indices(query, candidate)
+    }
+
+    fn requires_all_chars(&self) -> bool {
+        self.inner.requires_all_chars()
+    }
+}
+
+/// Boosts candidates whose last path segment matches the last segment of the query.
+///
+/// For the query `"tokio spawn"` or `"tokio::spawn"` this prefers `tokio::spawn`
+/// over `tokio::runtime::Runtime::spawn_blocking`. An exact name match gets the
+/// full bonus, a name starting with the query's last segment half of it.
+pub struct LastSegmentBoost<R> {
+    inner: R,
+    bonus: i64,
+}
+
+impl<R: Ranker> LastSegmentBoost<R> {
+    /// Wraps `inner` with the default bonus.
+    pub fn new(inner: R) -> Self {
+        Self { inner, bonus: 60 }
+    }
+
+    /// Sets the score added to exact name matches.
+    pub fn bonus(mut self, bonus: i64) -> Self {
+        self.bonus = bonus;
+        self
+    }
+}
+
+impl<R: Ranker> Ranker for LastSegmentBoost<R> {
+    fn score(&self, query: &str, candidate: &Candidate<'_>) -> Option<i64> {
+        let score = self.inner.score(query, candidate)?;
+
+        let Some(last) = query
+            .rsplit(|c: char| c == ':' || c.is_whitespace())
+            .find(|segment| !segment.is_empty())
+        else {
+            return Some(score);
+        };
+
+        let name = candidate.lower_name();
+        let bonus = if name == last {
+            self.bonus
+        } else if name.starts_with(last) {
+            self.bonus / 2
+        } else {
+            0
+        };
+
+        Some(score + bonus)
+    }
+
+    fn indices(&self, query: &str, candidate: &Candidate<'_>) -> Option<Vec<usize>> {
+        self.inner.indices(query, candidate)
+    }
+
+    fn requires_all_chars(&self) -> bool {
+        self.inner.requires_all_chars()
+    }
+}
+
+/// Adds a weight per [`ItemKind`] to the score.
+///
+/// By default types (structs, enums, unions, traits, type aliases and primitives)
+/// rank above modules, functions and macros, which rank above methods, fields and variants.
+pub struct KindWeights<R> {
+    inner: R,
+    weights: HashMap<ItemKind, i64>,
+}
+
+impl<R: Ranker> KindWeights<R> {
+    /// Wraps `inner` with the default weights.
+    pub fn new(inner: R) -> Self {
+        let weights = [
+            (ItemKind::Struct, 40),
+            (ItemKind::Enum, 40),
+            (ItemKind::Union, 40),
+            (ItemKind::Trait, 40),
+            (ItemKind::TypeAlias, 30),
+            (ItemKind::Primitive, 30),
+            (ItemKind::Module, 20),
+            (ItemKind::Function, 20),
+            (ItemKind::Macro, 20),
+            (ItemKind::Constant, 10),
+            (ItemKind::Static, 10),
+        ]
+        .into_iter()
+        .collect();
+
+        Self { inner, weights }
+    }
+
+    /// Sets the weight of a kind, kinds without a weight get `0`.
+    pub fn weight(mut self, kind: ItemKind, weight: i64) -> Self {
+        self.weights.insert(kind, weight);
+        self
+    }
+}
+
+impl<R: Ranker> Ranker for KindWeights<R> {
+    fn score(&self, query: &str, candidate: &Candidate<'_>) -> Option<i64> {
+        let score = self.inner.score(query, candidate)?;
+        let weight = candidate
+            .item()
+            .kind
+            .and_then(|kind| self.weights.get(&kind))
+            .copied()
+            .unwrap_or_default();
+
+        Some(score + weight)
+    }
+
+    fn indices(&self, query: &str, candidate: &Candidate<'_>) -> Option<Vec<usize>> {
+        self.inner.indices(query, candidate)
+    }
+
+    fn requires_all_chars(&self) -> bool {
+        self.inner.requires_all_chars()
+    }
+}
+
+/// Subtracts a penalty from deprecated and `#[doc(hidden)]` items.
+pub struct Penalize<R> {
+    inner: R,
+    deprecated: i64,
+    hidden: i64,
+}
+
+impl<R: Ranker> Penalize<R> {
+    /// Wraps `inner` with the default penalties.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            deprecated: 50,
+            hidden: 100,
+        }
+    }
+
+    /// Sets the penalty for deprecated items.
+    pub fn deprecated(mut self, penalty: i64) -> Self {
+        self.deprecated = penalty;
+        self
+    }
+
+    /// Sets the penalty for `#[doc(hidden)]` items.
+    pub fn hidden(mut self, penalty: i64) -> Self {
+        self.hidden = penalty;
+        self
+    }
+}
+
+impl<R: Ranker> Ranker for Penalize<R> {
+    fn score(&self, query: &str, candidate: &Candidate<'_>) -> Option<i64> {
+        let mut score = self.inner.score(query, candidate)?;
+        let item = candidate.item();
+
+        if item.deprecation.is_some() {
+            score -= self.deprecated;
+        }
+        if is_doc_hidden(&item.attributes) {
+            score -= self.hidden;
+        }
+
+        Some(score)
+    }
+
+    fn indices(&self, query: &str, candidate: &Candidate<'_>) -> Option<Vec<usize>> {
+        self.inner.indices(query, candidate)
+    }
+
+    fn requires_all_chars(&self) -> bool {
+        self.inner.requires_all_chars()
+    }
+}
+
+pub(crate) fn is_doc_hidden(attributes: &[Attribute]) -> bool {
+    attributes.iter().any(|attr| {
+        matches!(attr, Attribute::Other(other) if other.replace(' ', "") == "#[doc(hidden)]")
+    })
+}
@@ -1,6 +1,6 @@
-use super::{Doc, Indexed, SearchKey};
+use super::{Candidate, Doc, Indexed, Ranker, SearchHit, SearchKey, char_mask};
 use crate::Item;
-use fuzzy_matcher::FuzzyMatcher;
+use std::collections::{HashMap, HashSet, hash_map::Entry};
 
 impl Doc<Indexed> {
     /// Performs fuzzy search on the indexed documentation
@@ -9,6 +9,10 @@ impl Doc<Indexed> {
     /// results ranked by similarity score. The search is case-insensitive and matches
     /// against fully qualified item paths (e.g., "std::vec::Vec::push").
     ///
+    /// Every item is returned at most once, even if several of its paths match, e.g.
+    /// through re-exports. The other paths of an item are in [`Item::aliases`]. A query
+    /// that is exactly the path of some items returns only those, see [`Doc::lookup`].
+    ///
     /// # Arguments
     ///
     /// - `query` - The search term to match against
@@ -34,47 +38,200 @@ impl Doc<Indexed> {
     /// # }
     /// ```
     pub fn search(&self, query: &str, n: impl Into<Option<usize>>) -> Option<Vec<&Item>> {
-        let index = &self.0.search_index;
-        let matcher = &self.0.matcher;
-        let lower_query = query.to_lowercase();
+        self.search_with(query, n, &self.0.ranker)
+    }
+
+    /// Performs fuzzy search on the indexed documentation with a custom [`Ranker`]
+    ///
+    /// Works like [`Doc::search`], but scores every candidate with `ranker`, which
+    /// lets frontends tune relevance per query. The items at exactly the queried path,
+    /// see [`Doc::lookup`], are still returned as the only results.
+    ///
+    /// # Arguments
+    ///
+    /// - `query` - The search term to match against
+    /// - `n` - Maximum numbers of results to return (None for all matches)
+    /// - `ranker` - The ranker scoring the candidates
+    ///
+    /// # Returns
+    ///
+    /// `Some(Vec<&Item>)` with matching items ranked by `ranker`, or `None` if no matches found.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::{Doc, KindWeights, SkimRanker};
+    /// let indexed_doc = Doc::from_json("path/to/docs.json")?.parse()?.build_search_index();
+    /// // Prefer types over methods and functions
+    /// let ranker = KindWeights::new(SkimRanker::default());
+    /// let results = indexed_doc.search_with("config", 5, &ranker);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_with<R: Ranker + ?Sized>(
+        &self,
+        query: &str,
+        n: impl Into<Option<usize>>,
+        ranker: &R,
+    ) -> Option<Vec<&Item>> {
+        let results = self.rank(query, n.into(), ranker)?;
 
-        let mut results = index
+        let items: Vec<_> = results
             .iter()
-            .filter_map(|search_key| {
-                matcher
-                    .fuzzy_match(&search_key.key.to_lowercase(), &lower_query)
-                    .map(|score| (score, search_key))
+            .filter_map(|(_, search_key)| self.0.items.get(&search_key.id))
+            .collect();
+
+        if items.is_empty() { None } else { Some(items) }
+    }
+
+    /// Performs fuzzy search and returns the score and matched characters of every result
+    ///
+    /// Works like [`Doc::search`], but instead of bare items it returns a [`SearchHit`]
+    /// per result holding the fuzzy score, the key that matched and the char indices
+    /// of the matched characters in that key, e.g. for highlighting.
+    ///
+    /// # Arguments
+    ///
+    /// - `query` - The search term to match against
+    /// - `n` - Maximum numbers of results to return (None for all matches)
+    ///
+    /// # Returns
+    ///
+    /// `Some(Vec<SearchHit>)` ranked by relevance, or `None` if no matches found.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// let indexed_doc = Doc::from_json("path/to/docs.json")?.parse()?.build_search_index();
+    /// for hit in indexed_doc.search_detailed("vec push", 5).unwrap_or_default() {
+    ///     println!("{} ({}) matched at {:?}", hit.matched_key, hit.score, hit.match_indices);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search_detailed(
+        &self,
+        query: &str,
+        n: impl Into<Option<usize>>,
+    ) -> Option<Vec<SearchHit<'_>>> {
+        self.search_detailed_with(query, n, &self.0.ranker)
+    }
+
+    /// Performs fuzzy search with a custom [`Ranker`], returning detailed results
+    ///
+    /// The combination of [`Doc::search_with`] and [`Doc::search_detailed`]: candidates
+    /// are scored and their matched characters located by `ranker`.
+    pub fn search_detailed_with<R: Ranker + ?Sized>(
+        &self,
+        query: &str,
+        n: impl Into<Option<usize>>,
+        ranker: &R,
+    ) -> Option<Vec<SearchHit<'_>>> {
+        let lower_query = query.to_lowercase();
+        let results = self.rank(query, n.into(), ranker)?;
+
+        let hits: Vec<_> = results
+            .into_iter()
+            .filter_map(|(score, search_key)| {
+                let candidate = Candidate {
+                    search_key,
+                    items: &self.0.items,
+                };
+                Some(SearchHit {
+                    item: self.0.items.get(&search_key.id)?,
+                    score,
+                    matched_key: &search_key.key,
+                    match_indices: ranker.indices(&lower_query, &candidate).unwrap_or_default(),
+                    origin: search_key.origin.as_ref(),
+                })
             })
-            .collect::<Vec<(i64, &SearchKey)>>();
+            .collect();
 
-        results.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.key.len().cmp(&b.1.key.len())));
+        if hits.is_empty() { None } else { Some(hits) }
+    }
 
-        if let Some(item) = results.iter().find_map(|(_, search_key)| {
-            if search_key.key.to_lowercase() == lower_query {
-                self.0.items.get(&search_key.id).filter(|i| !i.name.is_empty())
-            } else {
-                None
-            }
-        }) {
-            return Some(vec![item]);
+    /// Scores all keys with `ranker` and returns the best `n` in order, one key per item.
+    ///
+    /// If items are reachable at exactly the query, see [`Doc::lookup`], only they are returned.
+    fn rank<R: Ranker + ?Sized>(
+        &self,
+        query: &str,
+        n: Option<usize>,
+        ranker: &R,
+    ) -> Option<Vec<(i64, &SearchKey)>> {
+        let index = &self.0.search_index;
+        let lower_query = query.to_lowercase();
+        let query_mask = if ranker.requires_all_chars() {
+            char_mask(&lower_query)
+        } else {
+            0
+        };
+
+        let score = |search_key| {
+            let candidate = Candidate {
+                search_key,
+                items: &self.0.items,
+            };
+            Some((ranker.score(&lower_query, &candidate)?, search_key))
+        };
+        let by_rank = |a: &(i64, &SearchKey), b: &(i64, &SearchKey)| {
+            b.0.cmp(&a.0)
+                .then(a.1.key.len().cmp(&b.1.key.len()))
+                .then_with(|| a.1.cmp(b.1))
+        };
+
+        let mut seen = HashSet::new();
+        let mut exact: Vec<_> = self
+            .exact_keys(query)
+            .filter_map(score)
+            .filter(|(_, search_key)| seen.insert(&search_key.id))
+            .collect();
+        if !exact.is_empty() {
+            exact.sort_unstable_by(by_rank);
+            exact.truncate(n.unwrap_or(usize::MAX));
+            return Some(exact);
         }
 
-        let n = n.into();
+        let results = index
+            .iter()
+            .filter(|search_key| search_key.mask & query_mask == query_mask)
+            .filter_map(score)
+            .collect::<Vec<(i64, &SearchKey)>>();
+
         if n == Some(0) || results.is_empty() {
             return None;
         }
 
-        let items: Vec<_> = results
-            .iter()
-            .filter_map(|(_, search_key)| self.0.items.get(&search_key.id))
-            .filter(|item| !item.name.is_empty())
-            .take(n.unwrap_or(usize::MAX))
-            .collect();
+        // Re-exports put several keys on the same item, only the best one of them is kept
+        let mut seen: HashMap<&str, usize> = HashMap::with_capacity(results.len());
+        let mut unique = Vec::with_capacity(results.len());
+        for result in results {
+            match seen.entry(&result.1.id) {
+                Entry::Occupied(entry) => {
+                    let best = &mut unique[*entry.get()];
+                    if by_rank(&result, best).is_lt() {
+                        *best = result;
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(unique.len());
+                    unique.push(result);
+                }
+            }
+        }
+        let mut results = unique;
 
-        if items.is_empty() {
-            None
-        } else {
-            Some(items)
+        // Only the best `n` results have to be in order, so partition them off
+        // first instead of sorting every match
+        if let Some(n) = n.filter(|&n| n < results.len()) {
+            results.select_nth_unstable_by(n - 1, by_rank);
+            results.truncate(n);
         }
+        results.sort_unstable_by(by_rank);
+
+        Some(results)
     }
 }
@@ -0,0 +1,580 @@
+use crate::Item;
+
+/// Rust keywords highlighted in code blocks
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "union",
+    "unsafe", "use", "where", "while",
+];
+
+/// The ANSI escapes turning a style on and off again
+type Style = (&'static str, &'static str);
+
+const BOLD: Style = ("\x1b[1m", "\x1b[22m");
+const UNDERLINE: Style = ("\x1b[4m", "\x1b[24m");
+const CODE: Style = ("\x1b[36m", "\x1b[39m");
+const KEYWORD: Style = ("\x1b[35m", "\x1b[39m");
+const TYPE: Style = ("\x1b[33m", "\x1b[39m");
+const STRING: Style = ("\x1b[32m", "\x1b[39m");
+const COMMENT: Style = ("\x1b[90m", "\x1b[39m");
+
+/// How [`TerminalOptions::render`] lays out Markdown for the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TerminalOptions {
+    /// The column paragraphs and list items are wrapped at, `None` to keep lines
+    /// as they are. Default `Some(80)`
+    pub width: Option<usize>,
+    /// Style headings, links and code with ANSI escapes and highlight Rust code.
+    /// Default `true`
+    pub color: bool,
+}
+
+impl Default for TerminalOptions {
+    fn default() -> Self {
+        Self {
+            width: Some(80),
+            color: true,
+        }
+    }
+}
+
+impl TerminalOptions {
+    /// Options following the environment
+    ///
+    /// Colors are turned off if `NO_COLOR` is set to a non-empty value (see
+    /// <https://no-color.org>), the width is taken from `COLUMNS` if it is set.
+    /// Whether the output actually goes to a terminal is up to the caller to check.
+    pub fn from_env() -> Self {
+        let width = std::env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.parse().ok())
+            .or(Self::default().width);
+        Self {
+            width,
+            color: std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()),
+        }
+    }
+
+    /// Renders Markdown docs for the terminal
+    ///
+    /// Paragraphs and list items are wrapped at [`TerminalOptions::width`], headings
+    /// are bold, Rust code blocks are indented and highlighted. Like rustdoc, hidden
+    /// lines of Rust code blocks (`# use std::fmt;`) are dropped and code blocks with
+    /// attributes like `no_run` or `edition2021` are Rust. Links are reduced to their
+    /// text and the definitions of reference links (`` [`Foo`]: crate::Foo ``) are left out.
+    ///
+    /// # Arguments
+    ///
+    /// - `markdown` - The Markdown to render, e.g. [`Item::docs`] or [`Doc::page`](crate::Doc::page)
+    ///
+    /// # Returns
+    ///
+    /// The rendered text, ending with a newline.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use docsrs::TerminalOptions;
+    /// let options = TerminalOptions {
+    ///     width: Some(40),
+    ///     color: false,
+    /// };
+    /// let text = options.render("# Usage\n\nCall [`spawn`] to run a task.\n\n[`spawn`]: crate::spawn");
+    /// assert_eq!(text, "Usage\n=====\n\nCall spawn to run a task.\n");
+    /// ```
+    pub fn render(&self, markdown: &str) -> String {
+        let mut renderer = Renderer {
+            options: self,
+            lines: Vec::new(),
+            paragraph: None,
+        };
+        for line in MarkdownLine::parse(markdown) {
+            match line {
+                MarkdownLine::Heading { level, text } => renderer.heading(level, text),
+                MarkdownLine::Code { line, rust } => {
+                    renderer.flush();
+                    renderer.lines.push(match rust {
+                        true => self.code_line(&line),
+                        false => format!("    {line}"),
+                    });
+                }
+                MarkdownLine::Text(line) => renderer.line(line),
+            }
+        }
+        renderer.flush();
+
+        while renderer.lines.last().is_some_and(String::is_empty) {
+            renderer.lines.pop();
+        }
+        let mut text = renderer.lines.join("\n");
+        text.push('\n');
+        text
+    }
+
+    /// Renders Rust code like the code blocks of [`TerminalOptions::render`],
+    /// indented and highlighted if colors are on
+    ///
+    /// Unlike in code blocks all lines are kept, `# ` doesn't hide a line.
+    ///
+    /// # Returns
+    ///
+    /// The rendered code, ending with a newline.
+    pub fn render_code(&self, code: &str) -> String {
+        code.lines()
+            .map(|line| format!("{}\n", self.code_line(line)))
+            .collect()
+    }
+
+    /// A line of a Rust code block, indented and highlighted
+    fn code_line(&self, line: &str) -> String {
+        if self.color {
+            format!("    {}", highlight_rust(line))
+        } else {
+            format!("    {line}")
+        }
+    }
+
+    /// Wraps `text` in the escapes of a style if colors are on
+    fn paint(&self, text: &str, (start, end): Style) -> String {
+        if self.color {
+            format!("{start}{text}{end}")
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Styles `inline code`, **bold** text and [links], dropping the link targets
+    fn inline(&self, line: &str) -> String {
+        MarkdownSpan::parse(line)
+            .into_iter()
+            .map(|span| match span {
+                MarkdownSpan::Text(text) => text.to_string(),
+                MarkdownSpan::Code(code) => self.paint(code, CODE),
+                MarkdownSpan::Bold(text) => self.paint(&self.inline(text), BOLD),
+                MarkdownSpan::Link(text) => self.paint(&self.inline(text), UNDERLINE),
+            })
+            .collect()
+    }
+}
+
+impl Item {
+    /// Renders the docs of the item for the terminal, see [`TerminalOptions::render`]
+    ///
+    /// # Returns
+    ///
+    /// The rendered docs, `None` if the item has no docs.
+    pub fn render_docs(&self, options: &TerminalOptions) -> Option<String> {
+        self.docs.as_deref().map(|docs| options.render(docs))
+    }
+}
+
+/// A line of Markdown docs as [`TerminalOptions::render`] sees it
+///
+/// For renderers of their own, e.g. with a TUI library, that style lines their
+/// own way but should show the same lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkdownLine<'a> {
+    /// An ATX heading like `## Examples`
+    Heading {
+        /// The number of `#`, from 1 to 6
+        level: usize,
+        /// The text of the heading with its inline Markdown
+        text: &'a str,
+    },
+    /// A line of a code block
+    Code {
+        /// The line as rustdoc shows it
+        line: String,
+        /// Whether the code block is Rust, see [`TerminalOptions::render`]
+        rust: bool,
+    },
+    /// Any other line with its inline Markdown, empty lines included
+    Text(&'a str),
+}
+
+impl<'a> MarkdownLine<'a> {
+    /// Splits Markdown into lines
+    ///
+    /// The fences of code blocks and the definitions of reference links
+    /// (`` [`Foo`]: crate::Foo ``) are left out. Like rustdoc, hidden lines of Rust
+    /// code blocks (`# use std::fmt;`) are dropped and `##` is unescaped to `#`,
+    /// other code blocks are kept as they are.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use docsrs::MarkdownLine;
+    /// let lines = MarkdownLine::parse("# Usage\n\n```\n# use std::fmt;\n##[derive(Debug)]\n```");
+    /// assert_eq!(lines[0], MarkdownLine::Heading { level: 1, text: "Usage" });
+    /// assert_eq!(lines[2], MarkdownLine::Code { line: "#[derive(Debug)]".into(), rust: true });
+    /// ```
+    pub fn parse(markdown: &'a str) -> Vec<Self> {
+        let mut lines = Vec::new();
+        // The fence of the code block we're in and whether it is Rust, `None` outside
+        // of code blocks
+        let mut fence: Option<(Fence, bool)> = None;
+
+        for line in markdown.lines() {
+            let trimmed = line.trim_start();
+            match fence {
+                Some((open, _)) if open.closes(line) => fence = None,
+                Some((_, true)) => lines.extend(
+                    visible_rust_line(line).map(|line| MarkdownLine::Code { line, rust: true }),
+                ),
+                Some((_, false)) => lines.push(MarkdownLine::Code {
+                    line: line.to_string(),
+                    rust: false,
+                }),
+                None => match Fence::open(line) {
+                    Some((open, info)) => fence = Some((open, is_rust_fence(info))),
+                    None if is_link_definition(trimmed) => {}
+                    None => lines.push(match heading(trimmed) {
+                        Some((level, text)) => MarkdownLine::Heading { level, text },
+                        None => MarkdownLine::Text(line),
+                    }),
+                },
+            }
+        }
+        lines
+    }
+}
+
+/// A piece of the inline Markdown of a line as [`TerminalOptions::render`] styles it
+///
+/// Like [`MarkdownLine`], for renderers of their own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkdownSpan<'a> {
+    /// Plain text
+    Text(&'a str),
+    /// `inline code`, without the backticks
+    Code(&'a str),
+    /// **Bold** text, with its inline Markdown
+    Bold(&'a str),
+    /// The text of a link with its inline Markdown, the target is dropped
+    Link(&'a str),
+}
+
+impl<'a> MarkdownSpan<'a> {
+    /// Splits a line of text, e.g. [`MarkdownLine::Text`], into spans
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use docsrs::MarkdownSpan;
+    /// let spans = MarkdownSpan::parse("Call [`spawn`](crate::spawn) **once**");
+    /// assert_eq!(
+    ///     spans,
+    ///     [MarkdownSpan::Text("Call "), MarkdownSpan::Link("`spawn`"), MarkdownSpan::Text(" "), MarkdownSpan::Bold("once")]
+    /// );
+    /// ```
+    pub fn parse(line: &'a str) -> Vec<Self> {
+        let mut spans = Vec::new();
+        // Where the plain text not pushed yet starts
+        let mut text = 0;
+        let mut next = 0;
+        while let Some(offset) = line[next..].find(['`', '[', '*']) {
+            let start = next + offset;
+            let rest = &line[start..];
+            let (span, end) = if let Some(code) = rest.strip_prefix('`')
+                && let Some(end) = code.find('`')
+            {
+                (MarkdownSpan::Code(&code[..end]), start + end + 2)
+            } else if let Some(bold) = rest.strip_prefix("**")
+                && let Some(end) = bold.find("**")
+            {
+                (MarkdownSpan::Bold(&bold[..end]), start + end + 4)
+            } else if let Some(link) = rest.strip_prefix('[')
+                && let Some(end) = link.find(']')
+            {
+                let after = skip_link_target(&link[end + 1..]);
+                (MarkdownSpan::Link(&link[..end]), line.len() - after.len())
+            } else {
+                next = start + 1;
+                continue;
+            };
+
+            if text < start {
+                spans.push(MarkdownSpan::Text(&line[text..start]));
+            }
+            spans.push(span);
+            text = end;
+            next = end;
+        }
+        if text < line.len() {
+            spans.push(MarkdownSpan::Text(&line[text..]));
+        }
+        spans
+    }
+}
+
+/// The text of a paragraph or list item, collected until it ends to be wrapped as a whole
+struct Paragraph {
+    /// What the first line starts with, e.g. `  • ` for list items
+    first: String,
+    /// What the following lines start with
+    rest: String,
+    text: String,
+}
+
+struct Renderer<'a> {
+    options: &'a TerminalOptions,
+    lines: Vec<String>,
+    paragraph: Option<Paragraph>,
+}
+
+impl Renderer<'_> {
+    /// Renders a line outside of code blocks
+    fn line(&mut self, line: &str) {
+        let trimmed = line.trim();
+        let indent = &line[..line.len() - line.trim_start().len()];
+
+        if trimmed.is_empty() {
+            self.flush();
+            if self.lines.last().is_some_and(|last| !last.is_empty()) {
+                self.lines.push(String::new());
+            }
+        } else if let Some((marker, text)) = list_item(trimmed) {
+            self.flush();
+            let first = format!("{indent}{marker} ");
+            let rest = " ".repeat(first.chars().count());
+            self.paragraph = Some(Paragraph {
+                first,
+                rest,
+                text: text.to_string(),
+            });
+        } else if trimmed.starts_with('|') {
+            // Tables would fall apart if wrapped
+            self.flush();
+            self.lines.push(self.options.inline(line));
+        } else if let Some(paragraph) = &mut self.paragraph {
+            paragraph.text.push(' ');
+            paragraph.text.push_str(trimmed);
+        } else {
+            self.paragraph = Some(Paragraph {
+                first: indent.to_string(),
+                rest: indent.to_string(),
+                text: trimmed.to_string(),
+            });
+        }
+    }
+
+    /// Renders a heading, underlined if colors are off
+    fn heading(&mut self, level: usize, heading: &str) {
+        self.flush();
+        let heading = self.options.inline(heading);
+        if self.options.color {
+            let heading = self.options.paint(&heading, BOLD);
+            self.lines.push(match level {
+                1 => self.options.paint(&heading, UNDERLINE),
+                _ => heading,
+            });
+        } else {
+            let width = heading.chars().count();
+            self.lines.push(heading);
+            match level {
+                1 => self.lines.push("=".repeat(width)),
+                2 => self.lines.push("-".repeat(width)),
+                _ => {}
+            }
+        }
+    }
+
+    /// Wraps and adds the current paragraph
+    fn flush(&mut self) {
+        let Some(paragraph) = self.paragraph.take() else {
+            return;
+        };
+        let text = self.options.inline(&paragraph.text);
+        let Some(width) = self.options.width else {
+            self.lines.push(format!("{}{text}", paragraph.first));
+            return;
+        };
+
+        let mut line = paragraph.first.clone();
+        let mut line_width = visible_width(&line);
+        let mut empty = true;
+        for word in text.split_whitespace() {
+            let word_width = visible_width(word);
+            if !empty && line_width + 1 + word_width > width {
+                self.lines.push(line);
+                line = paragraph.rest.clone();
+                line_width = visible_width(&line);
+                empty = true;
+            }
+            if !empty {
+                line.push(' ');
+                line_width += 1;
+            }
+            line.push_str(word);
+            line_width += word_width;
+            empty = false;
+        }
+        self.lines.push(line);
+    }
+}
+
+/// The level and text of an ATX heading, e.g. `## Examples`
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6)
+        .contains(&level)
+        .then(|| (level, text.trim().trim_end_matches('#').trim_end()))
+}
+
+/// The marker to print and the text of a list item, `•` for bullets, e.g. `- item`
+/// and `1.` for numbered items
+fn list_item(line: &str) -> Option<(&str, &str)> {
+    if let Some(text) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|bullet| line.strip_prefix(bullet))
+    {
+        return Some(("  •", text));
+    }
+
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let text = line[digits..].strip_prefix(". ")?;
+    (digits > 0).then(|| (&line[..digits + 1], text))
+}
+
+/// The fence opening a code block, at least three backticks or tildes
+#[derive(Debug, Clone, Copy)]
+struct Fence {
+    marker: char,
+    len: usize,
+}
+
+impl Fence {
+    /// The fence a line opens a code block with, and the info string after it
+    fn open(line: &str) -> Option<(Self, &str)> {
+        let trimmed = line.trim_start();
+        let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+        let len = trimmed.len() - trimmed.trim_start_matches(marker).len();
+        (len >= 3).then(|| (Self { marker, len }, &trimmed[len..]))
+    }
+
+    /// Whether a line closes the code block, with a fence of the same character at
+    /// least as long and nothing after it
+    fn closes(&self, line: &str) -> bool {
+        let trimmed = line.trim();
+        let len = trimmed.len() - trimmed.trim_start_matches(self.marker).len();
+        len >= self.len && len == trimmed.len()
+    }
+}
+
+/// Whether a code block with the given info string is Rust, which unlabeled blocks are
+pub(super) fn is_rust_fence(info: &str) -> bool {
+    fence_attributes(info).all(|attr| {
+        attr == "rust"
+            || attr.starts_with("edition")
+            || attr.starts_with("ignore-")
+            || matches!(
+                attr,
+                "ignore" | "no_run" | "should_panic" | "compile_fail" | "test_harness"
+            )
+    })
+}
+
+/// The attributes of a code block's info string, e.g. `rust` and `no_run` of `rust,no_run`
+pub(super) fn fence_attributes(info: &str) -> impl Iterator<Item = &str> {
+    info.split([',', ' ', '\t']).filter(|attr| !attr.is_empty())
+}
+
+/// A line of a Rust code block as rustdoc shows it, `None` for hidden lines
+///
+/// `# ` hides a line and `##` escapes a line that starts with `#`, like `#[derive]`
+/// written as `##[derive]`.
+pub(super) fn visible_rust_line(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if trimmed == "#" || trimmed.starts_with("# ") {
+        return None;
+    }
+    match trimmed.strip_prefix("##") {
+        Some(rest) => Some(format!("{}#{rest}", &line[..line.len() - trimmed.len()])),
+        None => Some(line.to_string()),
+    }
+}
+
+/// Whether a line defines the target of a reference link, e.g. ``[`Foo`]: crate::Foo``
+pub(super) fn is_link_definition(line: &str) -> bool {
+    line.starts_with('[')
+        && line
+            .find("]:")
+            .is_some_and(|end| !line[1..end].contains(']'))
+}
+
+/// Skips the `(target)` or `[reference]` following the text of a link
+fn skip_link_target(rest: &str) -> &str {
+    for (open, close) in [('(', ')'), ('[', ']')] {
+        if let Some(target) = rest.strip_prefix(open)
+            && let Some(end) = target.find(close)
+        {
+            return &target[end + 1..];
+        }
+    }
+    rest
+}
+
+/// The number of columns a string takes up, not counting ANSI escapes
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in text.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => width += 1,
+        }
+    }
+    width
+}
+
+/// Highlights keywords, types, string literals and comments of a line of Rust
+fn highlight_rust(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() * 2);
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let (token, style) = if rest.starts_with("//") {
+            (rest, Some(COMMENT))
+        } else if c == '"' {
+            // the closing quote, skipping escaped ones
+            let end = rest
+                .char_indices()
+                .skip(1)
+                .scan(false, |escaped, (i, c)| {
+                    let closes = c == '"' && !*escaped;
+                    *escaped = c == '\\' && !*escaped;
+                    Some((i, closes))
+                })
+                .find(|(_, closes)| *closes)
+                .map_or(rest.len(), |(i, _)| i + 1);
+            (&rest[..end], Some(STRING))
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            let style = if KEYWORDS.contains(&word) {
+                Some(KEYWORD)
+            } else if word.starts_with(char::is_uppercase) {
+                Some(TYPE)
+            } else {
+                None
+            };
+            (word, style)
+        } else {
+            (&rest[..c.len_utf8()], None)
+        };
+
+        match style {
+            Some((start, end)) => out.push_str(&format!("{start}{token}{end}")),
+            None => out.push_str(token),
+        }
+        rest = &rest[token.len()..];
+    }
+    out
+}
@@ -1,13 +1,18 @@
+mod coverage;
 mod enums;
 mod index;
 mod item;
 mod structs;
 mod traits;
+mod tree;
 mod unions;
 
 use super::Doc;
-pub use item::Item;
+pub use coverage::{Coverage, CoverageReport, DocIssue, DocLint};
+pub use index::IndexOptions;
+pub use item::{Item, PathPreference};
 use rustdoc_types::Crate;
+pub use tree::ModuleTree;
 
 /// Represents parsed documentation data with a structured AST.
 ///
@@ -0,0 +1,430 @@
+use super::{Doc, Parsed};
+use crate::doc::indexed::is_doc_hidden;
+use rustdoc_types::{Id, Item, ItemEnum, ItemKind, StructKind, Type, VariantKind, Visibility};
+use std::collections::{HashMap, HashSet};
+
+/// How many of a group of public items are documented, see [`CoverageReport`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coverage {
+    /// The number of items with docs
+    pub documented: usize,
+    /// The number of items
+    pub total: usize,
+}
+
+impl Coverage {
+    /// The number of items without docs
+    pub fn undocumented(&self) -> usize {
+        self.total - self.documented
+    }
+
+    /// The share of documented items in percent, 100 if there are no items
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.documented as f64 * 100.0 / self.total as f64
+        }
+    }
+
+    fn count(&mut self, documented: bool) {
+        self.total += 1;
+        self.documented += usize::from(documented);
+    }
+}
+
+/// What is wrong with the docs of an item.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum DocLint {
+    /// The item has no docs
+    Undocumented,
+    /// A function lacks a section its docs should have
+    MissingSection {
+        /// The heading of the missing section: `Safety`, `Errors`, `Panics` or `Examples`
+        section: String,
+    },
+    /// An intra-doc link rustdoc couldn't resolve
+    BrokenLink {
+        /// The destination of the link as written, e.g. `` `Config::new` ``
+        link: String,
+    },
+}
+
+/// A problem with the docs of a public item, see [`CoverageReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DocIssue {
+    /// The canonical path of the item
+    pub path: Vec<String>,
+    /// The kind of the item
+    pub kind: ItemKind,
+    /// What is wrong
+    pub lint: DocLint,
+}
+
+/// The documentation coverage of a crate's public API, returned by [`Doc::coverage`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoverageReport {
+    /// All public items
+    pub total: Coverage,
+    /// The public items by kind, sorted by the name of the kind
+    pub by_kind: Vec<(ItemKind, Coverage)>,
+    /// The public items by the canonical path of the module they are in, sorted by
+    /// path. Modules count towards themselves.
+    pub by_module: Vec<(Vec<String>, Coverage)>,
+    /// The problems found, sorted by path
+    pub issues: Vec<DocIssue>,
+}
+
+impl CoverageReport {
+    /// The public items without docs
+    pub fn undocumented(&self) -> impl Iterator<Item = &DocIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.lint == DocLint::Undocumented)
+    }
+}
+
+impl Doc<Parsed> {
+    /// Measures how well the public API of the crate is documented
+    ///
+    /// Every item reachable from outside the crate is counted: the items of public
+    /// modules and re-exports, the public fields and variants of types, the items of
+    /// traits and the public items of inherent impls. `#[doc(hidden)]` items are left
+    /// out. Items of other crates re-exported by this one are left out, too.
+    ///
+    /// Documented functions are checked for the sections the Rust API guidelines ask for:
+    /// `# Safety` for `unsafe fn`, `# Errors` for functions returning a `Result`,
+    /// `# Panics` if the docs say it panics (not "never panics") and `# Examples`
+    /// for every function.
+    /// The docs of all items are checked for intra-doc links rustdoc couldn't resolve.
+    ///
+    /// # Returns
+    ///
+    /// A [`CoverageReport`] with the counts and the problems found.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// let parsed_doc = Doc::from_json("path/to/docs.json")?.parse()?;
+    /// let report = parsed_doc.coverage();
+    /// println!("{:.1}% documented", report.total.percentage());
+    /// for issue in report.undocumented() {
+    ///     println!("undocumented: {}", issue.path.join("::"));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn coverage(&self) -> CoverageReport {
+        let mut public: Vec<(&Id, Vec<String>)> = self
+            .module_tree()
+            .public_paths()
+            .into_iter()
+            .filter_map(|(id, paths)| Some((id, paths.into_iter().next()?)))
+            .collect();
+        public.sort_by(|a, b| a.1.cmp(&b.1));
+
+        let mut counter = Counter::default();
+        for (id, path) in public {
+            let Some(item) = self.0.ast.index.get(id) else {
+                continue;
+            };
+            if item.crate_id != 0 || matches!(item.inner, ItemEnum::Use(_) | ItemEnum::Impl(_)) {
+                continue;
+            }
+
+            let module = match item.inner {
+                ItemEnum::Module(_) => path.clone(),
+                _ => path[..path.len() - 1].to_vec(),
+            };
+            self.count(item, path, &module, &mut counter);
+        }
+
+        let mut by_kind: Vec<_> = counter.by_kind.into_iter().collect();
+        by_kind.sort_by_cached_key(|(kind, _)| format!("{kind:?}"));
+        let mut by_module: Vec<_> = counter.by_module.into_iter().collect();
+        by_module.sort_by(|a, b| a.0.cmp(&b.0));
+        // stable, so the issues of an item stay in the order they were found
+        counter.issues.sort_by(|a, b| a.path.cmp(&b.path));
+
+        CoverageReport {
+            total: counter.total,
+            by_kind,
+            by_module,
+            issues: counter.issues,
+        }
+    }
+
+    /// Counts an item and its members
+    fn count(&self, item: &Item, path: Vec<String>, module: &[String], counter: &mut Counter) {
+        let Some(kind) = self.get_item_kind(&item.id) else {
+            return;
+        };
+        let docs = item.docs.as_deref().filter(|docs| !docs.trim().is_empty());
+        counter.total.count(docs.is_some());
+        counter
+            .by_kind
+            .entry(kind)
+            .or_default()
+            .count(docs.is_some());
+        counter
+            .by_module
+            .entry(module.to_vec())
+            .or_default()
+            .count(docs.is_some());
+
+        let mut issue = |lint| {
+            counter.issues.push(DocIssue {
+                path: path.clone(),
+                kind,
+                lint,
+            })
+        };
+        match docs {
+            None => issue(DocLint::Undocumented),
+            Some(docs) => {
+                if let ItemEnum::Function(function) = &item.inner {
+                    for section in missing_sections(docs, function) {
+                        let section = section.to_string();
+                        issue(DocLint::MissingSection { section });
+                    }
+                }
+                for link in broken_links(docs, &item.links) {
+                    issue(DocLint::BrokenLink { link });
+                }
+            }
+        }
+
+        for member in self.members(item) {
+            if let Some(name) = &member.name {
+                let mut path = path.clone();
+                path.push(name.clone());
+                self.count(member, path, module, counter);
+            }
+        }
+    }
+
+    /// The public named fields, variants, trait items and inherent impl items of an item
+    fn members(&self, item: &Item) -> Vec<&Item> {
+        let (members, impls): (&[Id], &[Id]) = match &item.inner {
+            ItemEnum::Struct(s) => match &s.kind {
+                StructKind::Plain { fields, .. } => (fields, &s.impls),
+                _ => (&[], &s.impls),
+            },
+            ItemEnum::Union(u) => (&u.fields, &u.impls),
+            ItemEnum::Enum(e) => (&e.variants, &e.impls),
+            ItemEnum::Variant(v) => match &v.kind {
+                VariantKind::Struct { fields, .. } => (fields, &[]),
+                _ => (&[], &[]),
+            },
+            ItemEnum::Trait(t) => (&t.items, &[]),
+            _ => (&[], &[]),
+        };
+
+        let index = &self.0.ast.index;
+        let inherent = impls
+            .iter()
+            .filter_map(|id| index.get(id))
+            .filter(|item| !is_doc_hidden(&item.attrs))
+            .filter_map(|item| match &item.inner {
+                ItemEnum::Impl(i) if i.trait_.is_none() => Some(&i.items),
+                _ => None,
+            })
+            .flatten();
+
+        members
+            .iter()
+            .chain(inherent)
+            .filter_map(|id| index.get(id))
+            .filter(|member| {
+                // variants and trait items are as public as their parent
+                let public = member.visibility == Visibility::Public
+                    || matches!(item.inner, ItemEnum::Enum(_) | ItemEnum::Trait(_));
+                public && !is_doc_hidden(&member.attrs)
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct Counter {
+    total: Coverage,
+    by_kind: HashMap<ItemKind, Coverage>,
+    by_module: HashMap<Vec<String>, Coverage>,
+    issues: Vec<DocIssue>,
+}
+
+/// The sections the docs of a function lack, in the order of the API guidelines
+fn missing_sections(docs: &str, function: &rustdoc_types::Function) -> Vec<&'static str> {
+    let mut headings = HashSet::new();
+    let mut mentions_panic = false;
+    let mut fenced = false;
+    for line in docs.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fenced = !fenced;
+        } else if fenced {
+            continue;
+        } else if let Some(heading) = trimmed.strip_prefix('#') {
+            headings.insert(heading.trim_start_matches('#').trim().to_lowercase());
+        } else {
+            mentions_panic |= says_it_panics(trimmed);
+        }
+    }
+
+    let returns_result = matches!(
+        &function.sig.output,
+        Some(Type::ResolvedPath(path)) if path.path.rsplit("::").next() == Some("Result")
+    );
+    let mut missing = Vec::new();
+    if function.header.is_unsafe && !headings.contains("safety") {
+        missing.push("Safety");
+    }
+    if returns_result && !headings.contains("errors") {
+        missing.push("Errors");
+    }
+    if mentions_panic && !headings.contains("panics") {
+        missing.push("Panics");
+    }
+    if !headings.contains("examples") && !headings.contains("example") {
+        missing.push("Examples");
+    }
+    missing
+}
+
+/// Whether a line of docs says the function panics
+///
+/// Negated mentions like "never panics" or "does not panic" don't count.
+fn says_it_panics(line: &str) -> bool {
+    const NEGATIONS: &[&str] = &[
+        "never", "not", "no", "without", "cannot", "can't", "won't", "doesn't", "don't",
+    ];
+    let line = line.to_lowercase().replace('’', "'");
+    line.match_indices("panic").any(|(start, _)| {
+        !line[..start]
+            .split_whitespace()
+            .rev()
+            .take(2)
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\''))
+            .any(|word| NEGATIONS.contains(&word))
+    })
+}
+
+/// The destinations of the intra-doc links of `docs` that rustdoc couldn't resolve
+///
+/// rustdoc lists the links it resolved by their destination as written, e.g.
+/// `` `Config::new` `` for ``[`Config::new`]``. Links to something that looks like
+/// a path but aren't listed are broken. Links to URLs and brackets around prose,
+/// like `[0, 1]`, are skipped, as are code blocks and code spans.
+fn broken_links(docs: &str, links: &HashMap<String, Id>) -> Vec<String> {
+    let mut definitions = HashMap::new();
+    let mut lines = Vec::new();
+    let mut fenced = false;
+    for line in docs.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fenced = !fenced;
+        } else if fenced {
+            continue;
+        } else if let Some((label, destination)) = link_definition(trimmed) {
+            definitions.insert(label, destination);
+        } else {
+            lines.push(trimmed);
+        }
+    }
+
+    let mut broken = Vec::new();
+    for line in lines {
+        let mut rest = line;
+        while let Some(start) = rest.find(['`', '[']) {
+            rest = &rest[start..];
+            if rest.starts_with('`') {
+                let ticks = &rest[..rest.len() - rest.trim_start_matches('`').len()];
+                match rest[ticks.len()..].find(ticks) {
+                    Some(end) => rest = &rest[ticks.len() + end + ticks.len()..],
+                    None => break,
+                }
+                continue;
+            }
+
+            // Indexing like `a[i]` or `v[0..n]` rather than a link
+            let before = line[..line.len() - rest.len()].chars().next_back();
+            if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                rest = &rest[1..];
+                continue;
+            }
+            let Some(close) = rest.find(']') else {
+                break;
+            };
+            let text = &rest[1..close];
+            let after = &rest[close + 1..];
+            let (destination, remaining) = if let Some(inline) = after.strip_prefix('(')
+                && let Some(end) = inline.find(')')
+            {
+                (inline[..end].trim(), &inline[end + 1..])
+            } else if let Some(reference) = after.strip_prefix('[')
+                && let Some(end) = reference.find(']')
+            {
+                let label = match &reference[..end] {
+                    "" => text,
+                    label => label,
+                };
+                let destination = definitions.get(label).copied().unwrap_or(label);
+                (destination, &reference[end + 1..])
+            } else {
+                (definitions.get(text).copied().unwrap_or(text), after)
+            };
+
+            if is_item_path(destination) && !links.contains_key(destination) {
+                broken.push(destination.to_string());
+            }
+            rest = remaining;
+        }
+    }
+    broken
+}
+
+/// Splits a link reference definition like `[label]: destination`
+fn link_definition(line: &str) -> Option<(&str, &str)> {
+    let (label, destination) = line.strip_prefix('[')?.split_once("]:")?;
+    Some((label, destination.trim()))
+}
+
+/// Whether a link destination names an item, like `` `Vec<T>::push` ``, `fn@spawn` or `hello!`
+fn is_item_path(destination: &str) -> bool {
+    let destination = destination.trim_matches('`');
+    let destination = destination
+        .split_once('@')
+        .map_or(destination, |(_, path)| path);
+    let destination = destination
+        .strip_suffix("()")
+        .or_else(|| destination.strip_suffix('!'))
+        .unwrap_or(destination);
+
+    let mut path = String::new();
+    let mut depth = 0usize;
+    for c in destination.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' => match depth.checked_sub(1) {
+                Some(outer) => depth = outer,
+                None => return false,
+            },
+            c if depth == 0 => path.push(c),
+            _ => {}
+        }
+    }
+
+    depth == 0
+        && !path.is_empty()
+        && !path.starts_with(|c: char| c.is_ascii_digit() || c == ':')
+        && path
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == ':')
+}
@@ -1,6 +1,6 @@
 use super::{Doc, Parsed};
 use crate::doc::indexed::SearchKey;
-use rustdoc_types::{Crate, Enum, Id, ItemEnum};
+use rustdoc_types::{Crate, Enum, Id, ItemEnum, VariantKind};
 use std::collections::HashMap;
 
 impl Doc<Parsed> {
@@ -12,14 +12,33 @@ impl Doc<Parsed> {
         parent_map: &HashMap<&'a Id, &'a Id>,
         path_cache: &mut HashMap<&'a Id, Vec<String>>,
     ) -> Vec<SearchKey> {
-        let variant_keys = enm.variants.iter().filter_map(move |variant_id| {
-            let variant_item = krate.index.get(variant_id)?;
-            let name = variant_item.name.as_deref()?;
-            Some(SearchKey {
-                id: variant_id.0.to_string(),
-                key: format!("{base_path}::{name}"),
+        let variant_keys = enm
+            .variants
+            .iter()
+            .filter_map(move |variant_id| {
+                let variant_item = krate.index.get(variant_id)?;
+                let name = variant_item.name.as_deref()?;
+                let variant_path = format!("{base_path}::{name}");
+
+                let field_keys = match &variant_item.inner {
+                    ItemEnum::Variant(variant) => match &variant.kind {
+                        VariantKind::Plain => Vec::new(),
+                        VariantKind::Tuple(fields) => {
+                            Self::search_keys_fields(krate, fields.iter().flatten(), &variant_path)
+                        }
+                        VariantKind::Struct { fields, .. } => {
+                            Self::search_keys_fields(krate, fields.iter(), &variant_path)
+                        }
+                    },
+                    _ => Vec::new(),
+                };
+
+                Some(
+                    std::iter::once(SearchKey::new(variant_id.0.to_string(), variant_path))
+                        .chain(field_keys),
+                )
             })
-        });
+            .flatten();
 
         let impl_keys = enm
             .impls
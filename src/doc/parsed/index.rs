@@ -1,7 +1,35 @@
-use super::{Doc, Parsed};
-use crate::{doc::indexed::SearchKey, Indexed};
+use super::{Doc, ModuleTree, Parsed};
+use crate::{
+    Indexed, Item,
+    doc::{
+        indexed::{SearchKey, is_blanket_impl},
+        signature::impl_header,
+    },
+};
 use rustdoc_types::{Crate, Id, Impl, ItemEnum, ItemKind, ItemSummary};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+/// Which impls end up in the index, see [`Doc::build_search_index_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexOptions {
+    /// Include blanket impls like `impl<T: Display> ToString for T`. Their methods
+    /// are indexed under the trait and every type they apply to. Default `true`
+    pub blanket_impls: bool,
+    /// Include the auto trait impls generated by the compiler (`Send`, `Sync`, ...).
+    /// They have no methods, so this only affects [`Doc::traits_implemented_by`]. Default `true`
+    pub synthetic_impls: bool,
+}
+
+impl Default for IndexOptions {
+    fn default() -> Self {
+        Self {
+            blanket_impls: true,
+            synthetic_impls: true,
+        }
+    }
+}
 
 impl Doc<Parsed> {
     /// Builds a fuzzy searchable index from the parsed documentation
@@ -15,17 +43,44 @@ impl Doc<Parsed> {
     ///
     /// A [`Doc<Indexed>`] that supports fuzzy search operations.
     pub fn build_search_index(&self) -> Doc<Indexed> {
+        self.build_search_index_with(IndexOptions::default())
+    }
+
+    /// Builds a fuzzy searchable index, choosing which impls to include
+    ///
+    /// Works like [`Doc::build_search_index`], but lets you leave out blanket impls
+    /// and the auto trait impls generated by the compiler, which can make up most
+    /// of the impls of a type.
+    ///
+    /// # Arguments
+    ///
+    /// - `options` - Which impls to include
+    ///
+    /// # Returns
+    ///
+    /// A [`Doc<Indexed>`] that supports fuzzy search operations.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::{Doc, IndexOptions};
+    /// let options = IndexOptions {
+    ///     blanket_impls: false,
+    ///     ..Default::default()
+    /// };
+    /// let indexed_doc = Doc::from_json("path/to/docs.json")?
+    ///     .parse()?
+    ///     .build_search_index_with(options);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_search_index_with(&self, options: IndexOptions) -> Doc<Indexed> {
         let krate = &self.0.ast;
 
-        // Build a map from child ID to parent module ID to discover re-export paths
-        let mut parent_map: HashMap<&Id, &Id> = HashMap::new();
-        for (id, item) in &krate.index {
-            if let ItemEnum::Module(m) = &item.inner {
-                for child_id in &m.items {
-                    parent_map.insert(child_id, id);
-                }
-            }
-        }
+        // The map from child ID to parent module ID is used to discover re-export paths
+        let tree = self.module_tree();
+        let parent_map = tree.parent_map();
 
         // A cache for recursively found paths
         let mut path_cache: HashMap<&Id, Vec<String>> = HashMap::new();
@@ -33,31 +88,105 @@ impl Doc<Parsed> {
         let mut index: Vec<SearchKey> = krate
             .paths
             .iter()
-            .filter_map(|(id, item)| self.generate_searchkeys(id, item, &parent_map, &mut path_cache))
+            .filter_map(|(id, item)| {
+                self.generate_searchkeys(id, item, parent_map, &mut path_cache)
+            })
             .flat_map(|vec| vec.into_iter())
             .collect();
 
-        // Add search keys for re-exports (Use items) that are not in `paths`
+        // Add search keys for re-exports (Use items) that are not in `paths`.
+        // The keys point at the re-exported item so both paths end up on the same `Item`
         for (id, item) in &krate.index {
             if krate.paths.contains_key(id) {
                 continue;
             }
-            if let ItemEnum::Use(_) = &item.inner {
-                if let Some(path) =
-                    self.get_item_path_recursive(id, &parent_map, &mut path_cache)
+            let ItemEnum::Use(u) = &item.inner else {
+                continue;
+            };
+
+            if u.is_glob {
+                // `pub use inner::*` makes every public item of `inner` reachable
+                // under the module containing the `use`
+                let Some(module_path) = parent_map.get(id).and_then(|module| {
+                    self.get_item_path_recursive(module, parent_map, &mut path_cache)
+                }) else {
+                    continue;
+                };
+                let module_path = module_path.join("::");
+                for child in tree.glob_items(id) {
+                    if let Some(name) = tree.name(child) {
+                        let target = self.resolve_use(child);
+                        index.push(SearchKey::new(
+                            target.0.to_string(),
+                            format!("{module_path}::{name}"),
+                        ));
+                    }
+                }
+            } else if let Some(path) = self.get_item_path_recursive(id, parent_map, &mut path_cache)
+            {
+                let key = path.join("::");
+                let target = self.resolve_use(id);
+                index.push(SearchKey::new(target.0.to_string(), key));
+            }
+        }
+
+        // Glob re-exports can overlap with each other and with the paths rustdoc
+        // already knows, e.g. for items of private modules
+        let mut seen = HashSet::new();
+        index.retain(|key| seen.insert((key.id.clone(), key.key.clone())));
+
+        // Blanket impls are copied to every type they apply to, so their methods
+        // are keyed once from the declaration instead of per type
+        let blanket_impls: Vec<&Impl> = krate
+            .index
+            .values()
+            .filter_map(|item| match &item.inner {
+                ItemEnum::Impl(impl_block)
+                    if options.blanket_impls
+                        && item.crate_id == 0
+                        && is_blanket_impl(impl_block)
+                        && impl_block.blanket_impl.is_none() =>
                 {
-                    let key = path.join("::");
-                    index.push(SearchKey {
-                        id: id.0.to_string(),
-                        key,
-                    });
+                    Some(impl_block)
+                }
+                _ => None,
+            })
+            .collect();
+        for impl_block in &blanket_impls {
+            index.extend(self.impl_method_keys(krate, impl_block, "", parent_map, &mut path_cache));
+        }
+
+        let mut items = self.build_items(krate.crate_version.clone(), &index);
+        for impl_block in blanket_impls {
+            let header = impl_header(impl_block);
+            for id in &impl_block.items {
+                if let Some(item) = items.get_mut(&id.0.to_string()) {
+                    item.blanket_impl = Some(header.clone());
                 }
             }
         }
+        Self::resolve_paths(&tree, &mut items);
 
-        let items = self.build_items(krate.crate_version.clone(), &index);
+        let impls = krate
+            .index
+            .iter()
+            .filter_map(|(id, item)| match &item.inner {
+                ItemEnum::Impl(impl_block)
+                    if (options.blanket_impls || !is_blanket_impl(impl_block))
+                        && (options.synthetic_impls || !impl_block.is_synthetic) =>
+                {
+                    Some((id.0.to_string(), impl_block.clone()))
+                }
+                _ => None,
+            })
+            .collect();
+        let paths = krate
+            .paths
+            .iter()
+            .map(|(id, summary)| (id.0.to_string(), summary.path.clone()))
+            .collect();
 
-        <Doc<Indexed>>::new(index, items)
+        <Doc<Indexed>>::new(index, items, impls, paths)
     }
 
     fn generate_searchkeys<'a>(
@@ -76,44 +205,41 @@ impl Doc<Parsed> {
         let base_path = item_summary.path.join("::");
         let kind = item_summary.kind;
 
-        let mut search_keys = vec![SearchKey {
-            id: id.0.to_string(),
-            key: base_path.clone(),
-        }];
+        let mut search_keys = vec![SearchKey::new(id.0.to_string(), base_path.clone())];
 
         match kind {
             ItemKind::Struct => {
-                if let Some(item) = krate.index.get(id) {
-                    if let ItemEnum::Struct(s) = &item.inner {
-                        search_keys.extend(self.search_keys_structs(
-                            krate, s, &base_path, parent_map, path_cache,
-                        ));
-                    }
+                if let Some(item) = krate.index.get(id)
+                    && let ItemEnum::Struct(s) = &item.inner
+                {
+                    search_keys.extend(
+                        self.search_keys_structs(krate, s, &base_path, parent_map, path_cache),
+                    );
                 }
             }
             ItemKind::Enum => {
-                if let Some(item) = krate.index.get(id) {
-                    if let ItemEnum::Enum(e) = &item.inner {
-                        search_keys.extend(
-                            self.search_keys_enums(krate, e, &base_path, parent_map, path_cache),
-                        );
-                    }
+                if let Some(item) = krate.index.get(id)
+                    && let ItemEnum::Enum(e) = &item.inner
+                {
+                    search_keys.extend(
+                        self.search_keys_enums(krate, e, &base_path, parent_map, path_cache),
+                    );
                 }
             }
             ItemKind::Trait => {
-                if let Some(item) = krate.index.get(id) {
-                    if let ItemEnum::Trait(t) = &item.inner {
-                        search_keys.extend(Self::search_keys_traits(krate, t, &base_path));
-                    }
+                if let Some(item) = krate.index.get(id)
+                    && let ItemEnum::Trait(t) = &item.inner
+                {
+                    search_keys.extend(Self::search_keys_traits(krate, t, &base_path));
                 }
             }
             ItemKind::Union => {
-                if let Some(item) = krate.index.get(id) {
-                    if let ItemEnum::Union(u) = &item.inner {
-                        search_keys.extend(self.search_keys_unions(
-                            krate, u, &base_path, parent_map, path_cache,
-                        ));
-                    }
+                if let Some(item) = krate.index.get(id)
+                    && let ItemEnum::Union(u) = &item.inner
+                {
+                    search_keys.extend(
+                        self.search_keys_unions(krate, u, &base_path, parent_map, path_cache),
+                    );
                 }
             }
             _ => {}
@@ -130,6 +256,12 @@ impl Doc<Parsed> {
         parent_map: &HashMap<&'a Id, &'a Id>,
         path_cache: &mut HashMap<&'a Id, Vec<String>>,
     ) -> Vec<SearchKey> {
+        // Synthetic impls have no items and the methods of blanket impls are keyed
+        // once from their declaration, not from the copy on every type
+        if impl_block.is_synthetic || impl_block.blanket_impl.is_some() {
+            return Vec::new();
+        }
+
         let path_to_use = if let Some(trait_path) = &impl_block.trait_ {
             self.get_item_path_recursive(&trait_path.id, parent_map, path_cache)
                 .map(|p| p.join("::"))
@@ -147,14 +279,83 @@ impl Doc<Parsed> {
             .filter_map(move |method_id| {
                 let method_item = krate.index.get(method_id)?;
                 let name = method_item.name.as_deref()?;
-                Some(SearchKey {
-                    id: method_id.0.to_string(),
-                    key: format!("{path_to_use}::{name}"),
-                })
+                Some(SearchKey::new(
+                    method_id.0.to_string(),
+                    format!("{path_to_use}::{name}"),
+                ))
             })
             .collect()
     }
 
+    /// Sets the definition and canonical paths of all items.
+    ///
+    /// Items in the module tree get them from the tree. Items below them, like
+    /// methods, fields and variants, take them from the module level item their
+    /// path starts with, e.g. `Config::timeout` from `Config`.
+    fn resolve_paths(tree: &ModuleTree, items: &mut HashMap<String, Item>) {
+        // definition and canonical path
+        type Paths = (Vec<String>, Option<Vec<String>>);
+
+        let public_paths = tree.public_paths();
+        let mut anchors: HashMap<Vec<String>, Paths> = HashMap::new();
+        for item in items.values_mut() {
+            let Ok(id) = item.id.parse::<u32>().map(Id) else {
+                continue;
+            };
+            let in_tree = &id == tree.root() || tree.parent(&id).is_some();
+            if in_tree && let Some(definition_path) = tree.definition_path(&id) {
+                item.definition_path = definition_path;
+                item.canonical_path = public_paths
+                    .get(&id)
+                    .and_then(|paths| paths.first())
+                    .cloned();
+                anchors.insert(
+                    item.path.clone(),
+                    (item.definition_path.clone(), item.canonical_path.clone()),
+                );
+            }
+        }
+
+        for item in items.values_mut() {
+            if !item.definition_path.is_empty() {
+                continue;
+            }
+
+            let anchor = (1..item.path.len())
+                .rev()
+                .find_map(|len| Some((len, anchors.get(&item.path[..len])?)));
+            match anchor {
+                Some((len, (definition_path, canonical_path))) => {
+                    let rest = &item.path[len..];
+                    item.definition_path = [definition_path, rest].concat();
+                    item.canonical_path = canonical_path
+                        .as_ref()
+                        .map(|canonical| [canonical, rest].concat());
+                }
+                None => item.definition_path = item.path.clone(),
+            }
+        }
+    }
+
+    /// Follows non-glob re-exports to the item they point at.
+    ///
+    /// Returns `id` itself if it isn't a re-export or the target isn't part of
+    /// this crate's index (e.g. re-exports from dependencies).
+    pub(super) fn resolve_use<'a>(&'a self, mut id: &'a Id) -> &'a Id {
+        let index = &self.0.ast.index;
+        // re-exports can't form cycles in valid code, the limit guards against broken input
+        for _ in 0..16 {
+            let Some(ItemEnum::Use(u)) = index.get(id).map(|item| &item.inner) else {
+                break;
+            };
+            match &u.id {
+                Some(target) if !u.is_glob && index.contains_key(target) => id = target,
+                _ => break,
+            }
+        }
+        id
+    }
+
     /// Recursively finds the path of an item by traversing up the module tree.
     /// This is a fallback for items not present in the `paths` map, like re-exports.
     pub(super) fn get_item_path_recursive<'a>(
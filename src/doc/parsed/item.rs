@@ -10,9 +10,13 @@ impl Doc<Parsed> {
         version: Option<String>,
         search_index: &[SearchKey],
     ) -> HashMap<String, Item> {
-        let mut items = HashMap::new();
+        let mut items: HashMap<String, Item> = HashMap::new();
         for sk in search_index {
-            if items.contains_key(&sk.id) {
+            if let Some(item) = items.get_mut(&sk.id) {
+                let alias: Vec<String> = sk.key.split("::").map(String::from).collect();
+                if item.path != alias && !item.aliases.contains(&alias) {
+                    item.aliases.push(alias);
+                }
                 continue;
             }
 
@@ -21,6 +25,11 @@ impl Doc<Parsed> {
                 if let Some(item) = self.0.ast.index.get(&id) {
                     let path: Vec<String> = sk.key.split("::").map(String::from).collect();
                     let kind = self.get_item_kind(&id);
+                    // re-exports of other crates' items have no name of their own
+                    let name = match &item.inner {
+                        ItemEnum::Use(u) => Some(u.name.clone()),
+                        _ => item.name.clone(),
+                    };
                     let links = item
                         .links
                         .iter()
@@ -33,15 +42,19 @@ impl Doc<Parsed> {
                             crate_id: item.crate_id,
                             crate_version: version.clone(),
                             path,
+                            aliases: Vec::new(),
+                            definition_path: Vec::new(),
+                            canonical_path: None,
                             kind,
                             visibility: item.visibility.clone(),
                             span: item.span.clone(),
-                            name: item.name.clone().unwrap_or_default(),
+                            name: name.unwrap_or_default(),
                             docs: item.docs.clone(),
                             links,
                             attributes: item.attrs.clone(),
                             deprecation: item.deprecation.clone(),
                             inner: item.inner.clone(),
+                            blanket_impl: None,
                         },
                     );
                 }
@@ -51,7 +64,7 @@ impl Doc<Parsed> {
     }
 
     /// Tries to determine the `ItemKind` of an item.
-    fn get_item_kind(&self, id: &Id) -> Option<ItemKind> {
+    pub(super) fn get_item_kind(&self, id: &Id) -> Option<ItemKind> {
         let item = self.0.ast.index.get(id)?;
         match &item.inner {
             ItemEnum::Module(_) => Some(ItemKind::Module),
@@ -92,7 +105,11 @@ impl Doc<Parsed> {
 /// struct, enum, module, etc.) extracted from the rustdoc AST. It provides a
 /// simplified and searchable representation of the original rustdoc data with
 /// preprocessed paths and normalized identifiers.
+///
+/// New fields may be added in minor releases, so items can only be built by this crate.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub struct Item {
     /// Unique identifier for this item within the documentation
     pub id: String,
@@ -102,6 +119,14 @@ pub struct Item {
     pub crate_version: Option<String>,
     /// Fully qualified path components (e.g., ["std", "collections", "HashMap"])
     pub path: Vec<String>,
+    /// Other paths the item is reachable by, like re-exports (e.g., ["std", "prelude", "v1", "Vec"])
+    pub aliases: Vec<Vec<String>>,
+    /// The path of the modules the item is declared in, including private ones
+    /// (e.g., ["tokio", "runtime", "task", "join", "JoinHandle"])
+    pub definition_path: Vec<String>,
+    /// The shortest path users can write, `None` if the item isn't publicly reachable
+    /// (e.g., ["tokio", "task", "JoinHandle"])
+    pub canonical_path: Option<Vec<String>>,
     /// The kind of the item
     pub kind: Option<ItemKind>,
     /// Source code location information, if available
@@ -120,9 +145,40 @@ pub struct Item {
     pub deprecation: Option<rustdoc_types::Deprecation>,
     /// The actual item type and data (struct, enum, function, etc.)
     pub inner: rustdoc_types::ItemEnum,
+    /// Header of the blanket impl this item is defined in (e.g. `impl<T: Display> ToString for T`)
+    pub blanket_impl: Option<String>,
+}
+
+/// Which of an [`Item`]'s paths to present, see [`Item::path_by`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PathPreference {
+    /// The path the item is defined at, [`Item::definition_path`]
+    #[default]
+    Definition,
+    /// The shortest of all paths the item is reachable by
+    Shortest,
+    /// The shortest public path, [`Item::canonical_path`], falling back to [`Item::path`]
+    Canonical,
 }
 
 impl Item {
+    /// Returns the path to present for this item.
+    ///
+    /// The shortest path is the one with the fewest segments, ties are broken by
+    /// length, e.g. the re-export `tokio::spawn` over `tokio::task::spawn::spawn`.
+    pub fn path_by(&self, preference: PathPreference) -> &[String] {
+        match preference {
+            PathPreference::Definition => &self.definition_path,
+            PathPreference::Canonical => self.canonical_path.as_ref().unwrap_or(&self.path),
+            PathPreference::Shortest => std::iter::once(&self.path)
+                .chain(&self.aliases)
+                .min_by_key(|path| (path.len(), path.iter().map(String::len).sum::<usize>()))
+                .unwrap_or(&self.path),
+        }
+    }
+
     /// Returns the url for the item on docs.rs
     pub fn url(&self) -> Result<Option<Url>, Error> {
         if self.path.is_empty() {
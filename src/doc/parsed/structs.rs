@@ -1,6 +1,6 @@
 use super::{Doc, Parsed};
 use crate::doc::indexed::SearchKey;
-use rustdoc_types::{Crate, Id, ItemEnum, Struct};
+use rustdoc_types::{Crate, Id, ItemEnum, Struct, StructKind};
 use std::collections::HashMap;
 
 impl Doc<Parsed> {
@@ -12,7 +12,17 @@ impl Doc<Parsed> {
         parent_map: &HashMap<&'a Id, &'a Id>,
         path_cache: &mut HashMap<&'a Id, Vec<String>>,
     ) -> Vec<SearchKey> {
-        strukt
+        let field_keys = match &strukt.kind {
+            StructKind::Unit => Vec::new(),
+            StructKind::Tuple(fields) => {
+                Self::search_keys_fields(krate, fields.iter().flatten(), base_path)
+            }
+            StructKind::Plain { fields, .. } => {
+                Self::search_keys_fields(krate, fields.iter(), base_path)
+            }
+        };
+
+        let impl_keys = strukt
             .impls
             .iter()
             .filter_map(move |impl_id| {
@@ -27,7 +37,28 @@ impl Doc<Parsed> {
                         .into_iter(),
                 )
             })
-            .flatten()
+            .flatten();
+
+        field_keys.into_iter().chain(impl_keys).collect()
+    }
+
+    /// Creates keys for the named or tuple fields of a struct, union or enum variant.
+    ///
+    /// Tuple fields are named by their position, e.g. `crate::Millis::0`.
+    pub(super) fn search_keys_fields<'a>(
+        krate: &Crate,
+        fields: impl Iterator<Item = &'a Id>,
+        base_path: &str,
+    ) -> Vec<SearchKey> {
+        fields
+            .filter_map(|field_id| {
+                let field = krate.index.get(field_id)?;
+                let name = field.name.as_deref()?;
+                Some(SearchKey::new(
+                    field_id.0.to_string(),
+                    format!("{base_path}::{name}"),
+                ))
+            })
             .collect()
     }
 }
@@ -14,10 +14,10 @@ impl Doc<Parsed> {
             .filter_map(move |item_id| {
                 let item = krate.index.get(item_id)?;
                 let name = item.name.as_deref()?;
-                Some(SearchKey {
-                    id: item_id.0.to_string(),
-                    key: format!("{base_path}::{name}"),
-                })
+                Some(SearchKey::new(
+                    item_id.0.to_string(),
+                    format!("{base_path}::{name}"),
+                ))
             })
             .collect()
     }
@@ -0,0 +1,313 @@
+use super::{Doc, Parsed};
+use crate::doc::indexed::is_doc_hidden;
+use rustdoc_types::{Id, Item, ItemEnum, ItemKind, Visibility};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Paths longer than this are not followed when resolving public paths, guarding against broken input
+const MAX_PATH_DEPTH: usize = 16;
+
+/// The module hierarchy of a crate, returned by [`Doc::module_tree`].
+///
+/// Children are listed the way they can be named from the module: the items of
+/// glob re-exports (`pub use inner::*`) are listed in place of the `use` and
+/// non-glob re-exports are listed as `Use` items carrying their new name.
+pub struct ModuleTree<'a> {
+    doc: &'a Doc<Parsed>,
+    parents: HashMap<&'a Id, &'a Id>,
+}
+
+impl Doc<Parsed> {
+    /// Builds the module tree of the crate
+    ///
+    /// # Returns
+    ///
+    /// A [`ModuleTree`] for navigating the modules and their items.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # fn main() -> Result<(), docsrs::Error> {
+    /// use docsrs::Doc;
+    /// let parsed_doc = Doc::from_json("path/to/docs.json")?.parse()?;
+    /// let tree = parsed_doc.module_tree();
+    /// for (depth, id) in tree.walk() {
+    ///     println!("{}{}", "  ".repeat(depth), tree.name(id).unwrap_or_default());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn module_tree(&self) -> ModuleTree<'_> {
+        let mut parents = HashMap::new();
+        for (id, item) in &self.0.ast.index {
+            if let ItemEnum::Module(m) = &item.inner {
+                for child_id in &m.items {
+                    parents.insert(child_id, id);
+                }
+            }
+        }
+
+        ModuleTree { doc: self, parents }
+    }
+}
+
+impl<'a> ModuleTree<'a> {
+    /// The crate's root module
+    pub fn root(&self) -> &'a Id {
+        &self.doc.0.ast.root
+    }
+
+    /// Returns the item with the given id
+    pub fn item(&self, id: &Id) -> Option<&'a Item> {
+        self.doc.0.ast.index.get(id)
+    }
+
+    /// The name an item is listed under, for re-exports the name it is re-exported as
+    pub fn name(&self, id: &Id) -> Option<&'a str> {
+        match &self.item(id)?.inner {
+            ItemEnum::Use(u) => Some(&u.name),
+            _ => self.item(id)?.name.as_deref(),
+        }
+    }
+
+    /// The kind of an item, for re-exports the kind of the re-exported item
+    pub fn kind(&self, id: &Id) -> Option<ItemKind> {
+        self.doc
+            .get_item_kind(id)
+            .or_else(|| match &self.item(id)?.inner {
+                // re-exports of other crates' items are only known by their summary
+                ItemEnum::Use(u) => Some(self.doc.0.ast.paths.get(u.id.as_ref()?)?.kind),
+                _ => None,
+            })
+    }
+
+    /// The module an item is declared in, `None` for the root and items outside modules
+    pub fn parent(&self, id: &Id) -> Option<&'a Id> {
+        self.parents.get(id).copied()
+    }
+
+    pub(super) fn parent_map(&self) -> &HashMap<&'a Id, &'a Id> {
+        &self.parents
+    }
+
+    /// The items of a module, with glob re-exports expanded
+    ///
+    /// Glob re-exports of modules and enums are replaced by the public items of
+    /// the module or the variants of the enum, recursively. Globs of other crates'
+    /// modules can't be expanded and are kept as the `Use` item. A `Use` of a
+    /// module is followed to the module.
+    ///
+    /// Returns the variants for an enum and an empty list if `id` is neither a
+    /// module nor an enum.
+    pub fn children(&self, id: &Id) -> Vec<&'a Id> {
+        let Some((id, _)) = self.doc.0.ast.index.get_key_value(id) else {
+            return Vec::new();
+        };
+
+        let mut children = Vec::new();
+        let mut visited = HashSet::new();
+        self.expand(self.doc.resolve_use(id), false, &mut children, &mut visited);
+        children
+    }
+
+    /// The items a glob re-export imports, empty if `id` isn't a glob `use` of an
+    /// item in this crate
+    pub(super) fn glob_items(&self, id: &'a Id) -> Vec<&'a Id> {
+        let mut items = Vec::new();
+        if let Some(ItemEnum::Use(u)) = self.item(id).map(|item| &item.inner)
+            && u.is_glob
+            && let Some(target) = &u.id
+        {
+            self.expand(target, true, &mut items, &mut HashSet::new());
+        }
+        items
+    }
+
+    /// Walks the whole tree depth-first, starting at the root
+    ///
+    /// Yields every item with its depth, the root has depth 0. Modules reachable
+    /// by several paths are only descended into the first time.
+    pub fn walk(&self) -> Vec<(usize, &'a Id)> {
+        self.walk_from(self.root())
+    }
+
+    /// Walks the subtree of a module depth-first, see [`ModuleTree::walk`]
+    pub fn walk_from(&self, id: &'a Id) -> Vec<(usize, &'a Id)> {
+        let mut walked = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![(0, id)];
+
+        while let Some((depth, id)) = stack.pop() {
+            walked.push((depth, id));
+
+            let module = self.doc.resolve_use(id);
+            if self.kind(module) == Some(ItemKind::Module) && visited.insert(module) {
+                stack.extend(
+                    self.children(module)
+                        .into_iter()
+                        .rev()
+                        .map(|child| (depth + 1, child)),
+                );
+            }
+        }
+
+        walked
+    }
+
+    /// Finds an item by its path through the module tree, e.g. `tokio::sync::Mutex`
+    ///
+    /// Re-exports are followed, so the path doesn't have to be the definition path.
+    pub fn find(&self, path: &str) -> Option<&'a Id> {
+        let mut segments = path.split("::");
+        if segments.next()? != self.name(self.root())? {
+            return None;
+        }
+
+        segments.try_fold(self.root(), |module, segment| {
+            self.children(module)
+                .into_iter()
+                .find(|child| self.name(child) == Some(segment))
+                .map(|child| self.doc.resolve_use(child))
+        })
+    }
+
+    /// The path of the module chain an item is declared in, e.g. `tokio::runtime::task::join::JoinHandle`
+    ///
+    /// Unlike the public paths this includes private modules. Private modules
+    /// aren't always part of the tree, in that case the path rustdoc recorded for
+    /// the item is used. `None` for items outside the module tree, like methods and fields.
+    pub fn definition_path(&self, id: &Id) -> Option<Vec<String>> {
+        let mut path = vec![self.name(id)?.to_string()];
+        let mut current = id;
+        while let Some(parent) = self.parent(current) {
+            path.push(self.name(parent)?.to_string());
+            current = parent;
+        }
+
+        if current == self.root() {
+            path.reverse();
+            Some(path)
+        } else {
+            self.doc
+                .0
+                .ast
+                .paths
+                .get(id)
+                .map(|summary| summary.path.clone())
+        }
+    }
+
+    /// Computes every path by which the items of the crate can be named from outside
+    ///
+    /// Starting at the root, public items are collected through public modules and
+    /// re-exports, including glob re-exports. Items with `pub(crate)` or `pub(in ..)`
+    /// visibility and `#[doc(hidden)]` items are skipped, as well as everything below them.
+    /// Re-exports are resolved, so the paths are listed under the id of the re-exported item.
+    ///
+    /// # Returns
+    ///
+    /// The public paths of every reachable item, shortest first. The first one is
+    /// the canonical path.
+    pub fn public_paths(&self) -> HashMap<&'a Id, Vec<Vec<String>>> {
+        let mut paths: HashMap<&'a Id, Vec<Vec<String>>> = HashMap::new();
+        let Some(root_name) = self.name(self.root()) else {
+            return paths;
+        };
+
+        // Breadth-first, so paths are found in order of their length
+        let mut queue =
+            VecDeque::from([(self.root(), vec![root_name.to_string()], vec![self.root()])]);
+        paths.insert(self.root(), vec![vec![root_name.to_string()]]);
+
+        while let Some((module, module_path, ancestors)) = queue.pop_front() {
+            for child in self.children(module) {
+                let target = self.doc.resolve_use(child);
+                let (Some(item), Some(name)) = (self.item(child), self.name(child)) else {
+                    continue;
+                };
+                let hidden = |item: &Item| is_doc_hidden(&item.attrs);
+                if item.visibility != Visibility::Public
+                    || hidden(item)
+                    || self.item(target).is_some_and(hidden)
+                {
+                    continue;
+                }
+
+                let mut path = module_path.clone();
+                path.push(name.to_string());
+
+                let known = paths.entry(target).or_default();
+                if known.contains(&path) {
+                    continue;
+                }
+                known.push(path.clone());
+
+                // Modules re-exported at several places are descended into from every
+                // path, but not into themselves when re-exporting one of their ancestors
+                if self.kind(target) == Some(ItemKind::Module)
+                    && !ancestors.contains(&target)
+                    && path.len() < MAX_PATH_DEPTH
+                {
+                    let mut ancestors = ancestors.clone();
+                    ancestors.push(target);
+                    queue.push_back((target, path, ancestors));
+                }
+            }
+        }
+
+        // Ties are broken in favor of the definition path, then alphabetically
+        for (id, paths) in &mut paths {
+            let definition_path = self.definition_path(id);
+            paths.sort_by_cached_key(|path| {
+                (
+                    path.len(),
+                    path.iter().map(String::len).sum::<usize>(),
+                    definition_path.as_ref() != Some(path),
+                    path.clone(),
+                )
+            });
+        }
+        paths
+    }
+
+    fn expand(
+        &self,
+        id: &'a Id,
+        public_only: bool,
+        children: &mut Vec<&'a Id>,
+        visited: &mut HashSet<&'a Id>,
+    ) {
+        // glob re-exports can form cycles, e.g. two modules `pub use`ing each other
+        if !visited.insert(id) {
+            return;
+        }
+
+        let items = match self.item(id).map(|item| &item.inner) {
+            Some(ItemEnum::Module(m)) => &m.items,
+            Some(ItemEnum::Enum(e)) => &e.variants,
+            _ => return,
+        };
+
+        for child in items {
+            let Some(item) = self.item(child) else {
+                continue;
+            };
+            // a glob only imports what is visible from the importing module
+            if public_only
+                && item.visibility != Visibility::Public
+                && !matches!(item.inner, ItemEnum::Variant(_))
+            {
+                continue;
+            }
+
+            match &item.inner {
+                ItemEnum::Use(u) if u.is_glob => match &u.id {
+                    Some(target) if self.item(target).is_some() => {
+                        self.expand(target, true, children, visited)
+                    }
+                    _ => children.push(child),
+                },
+                _ => children.push(child),
+            }
+        }
+    }
+}
@@ -12,7 +12,9 @@ impl Doc<Parsed> {
         parent_map: &HashMap<&'a Id, &'a Id>,
         path_cache: &mut HashMap<&'a Id, Vec<String>>,
     ) -> Vec<SearchKey> {
-        union
+        let field_keys = Self::search_keys_fields(krate, union.fields.iter(), base_path);
+
+        let impl_keys = union
             .impls
             .iter()
             .filter_map(move |impl_id| {
@@ -27,7 +29,8 @@ impl Doc<Parsed> {
                         .into_iter(),
                 )
             })
-            .flatten()
-            .collect()
+            .flatten();
+
+        field_keys.into_iter().chain(impl_keys).collect()
     }
 }
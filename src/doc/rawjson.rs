@@ -47,6 +47,11 @@ impl Doc<RawJson> {
         Ok(Doc(RawJson(json)))
     }
 
+    /// The size of the JSON data in bytes
+    pub fn size(&self) -> usize {
+        self.0.0.len()
+    }
+
     /// Parses the raw JSON data into a structured documentation AST.
     ///
     /// Deserializes the JSON bytes into a rustdoc AST structure, making the
@@ -0,0 +1,447 @@
+use crate::Item;
+use rustdoc_types::{
+    Abi, AssocItemConstraint, AssocItemConstraintKind, GenericArg, GenericArgs, GenericBound,
+    GenericParamDef, GenericParamDefKind, Generics, Impl, ItemEnum, Path, Term, TraitBoundModifier,
+    Type, VariantKind, WherePredicate,
+};
+
+/// Renders a type back into Rust syntax.
+///
+/// Paths are printed the way they are written in the source (`Vec<T>`, not
+/// `alloc::vec::Vec<T>`), the same way rustdoc shows them.
+pub(crate) fn type_to_string(ty: &Type) -> String {
+    match ty {
+        Type::ResolvedPath(path) => path_to_string(path),
+        Type::DynTrait(dyn_trait) => {
+            let mut bounds: Vec<String> = dyn_trait
+                .traits
+                .iter()
+                .map(|poly| {
+                    format!(
+                        "{}{}",
+                        higher_ranked(&poly.generic_params),
+                        path_to_string(&poly.trait_)
+                    )
+                })
+                .collect();
+            bounds.extend(dyn_trait.lifetime.clone());
+            format!("dyn {}", bounds.join(" + "))
+        }
+        Type::Generic(name) | Type::Primitive(name) => name.clone(),
+        Type::FunctionPointer(fn_ptr) => {
+            let inputs: Vec<String> = fn_ptr
+                .sig
+                .inputs
+                .iter()
+                .map(|(_, ty)| type_to_string(ty))
+                .collect();
+            let output = fn_ptr
+                .sig
+                .output
+                .as_ref()
+                .map(|ty| format!(" -> {}", type_to_string(ty)))
+                .unwrap_or_default();
+            let unsafety = if fn_ptr.header.is_unsafe {
+                "unsafe "
+            } else {
+                ""
+            };
+            format!(
+                "{}{unsafety}fn({}){output}",
+                higher_ranked(&fn_ptr.generic_params),
+                inputs.join(", ")
+            )
+        }
+        Type::Tuple(types) if types.len() == 1 => format!("({},)", type_to_string(&types[0])),
+        Type::Tuple(types) => format!(
+            "({})",
+            types
+                .iter()
+                .map(type_to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Type::Slice(ty) => format!("[{}]", type_to_string(ty)),
+        Type::Array { type_, len } => format!("[{}; {len}]", type_to_string(type_)),
+        Type::Pat { type_, .. } => type_to_string(type_),
+        Type::ImplTrait(bounds) => format!("impl {}", bounds_to_string(bounds)),
+        Type::Infer => "_".to_string(),
+        Type::RawPointer { is_mutable, type_ } => {
+            let mutability = if *is_mutable { "mut" } else { "const" };
+            format!("*{mutability} {}", type_to_string(type_))
+        }
+        Type::BorrowedRef {
+            lifetime,
+            is_mutable,
+            type_,
+        } => {
+            let lifetime = lifetime
+                .as_ref()
+                .map(|lifetime| format!("{lifetime} "))
+                .unwrap_or_default();
+            let mutability = if *is_mutable { "mut " } else { "" };
+            format!("&{lifetime}{mutability}{}", type_to_string(type_))
+        }
+        Type::QualifiedPath {
+            name,
+            args,
+            self_type,
+            trait_,
+        } => {
+            let args = args
+                .as_deref()
+                .map(generic_args_to_string)
+                .unwrap_or_default();
+            match trait_ {
+                // `Self::Output` in a trait has a trait path without a name
+                Some(trait_) if !trait_.path.is_empty() => format!(
+                    "<{} as {}>::{name}{args}",
+                    type_to_string(self_type),
+                    path_to_string(trait_)
+                ),
+                _ => format!("{}::{name}{args}", type_to_string(self_type)),
+            }
+        }
+    }
+}
+
+pub(crate) fn path_to_string(path: &Path) -> String {
+    let args = path
+        .args
+        .as_deref()
+        .map(generic_args_to_string)
+        .unwrap_or_default();
+    format!("{}{args}", path.path)
+}
+
+fn generic_args_to_string(args: &GenericArgs) -> String {
+    match args {
+        GenericArgs::AngleBracketed { args, constraints } => {
+            let args = args
+                .iter()
+                .map(|arg| match arg {
+                    GenericArg::Lifetime(lifetime) => lifetime.clone(),
+                    GenericArg::Type(ty) => type_to_string(ty),
+                    GenericArg::Const(constant) => constant.expr.clone(),
+                    GenericArg::Infer => "_".to_string(),
+                })
+                .chain(constraints.iter().map(constraint_to_string))
+                .collect::<Vec<_>>();
+
+            if args.is_empty() {
+                String::new()
+            } else {
+                format!("<{}>", args.join(", "))
+            }
+        }
+        GenericArgs::Parenthesized { inputs, output } => {
+            let inputs: Vec<String> = inputs.iter().map(type_to_string).collect();
+            let output = output
+                .as_ref()
+                .map(|ty| format!(" -> {}", type_to_string(ty)))
+                .unwrap_or_default();
+            format!("({}){output}", inputs.join(", "))
+        }
+        GenericArgs::ReturnTypeNotation => "(..)".to_string(),
+    }
+}
+
+fn constraint_to_string(constraint: &AssocItemConstraint) -> String {
+    let args = constraint
+        .args
+        .as_deref()
+        .map(generic_args_to_string)
+        .unwrap_or_default();
+    match &constraint.binding {
+        AssocItemConstraintKind::Equality(term) => {
+            format!("{}{args} = {}", constraint.name, term_to_string(term))
+        }
+        AssocItemConstraintKind::Constraint(bounds) => {
+            format!("{}{args}: {}", constraint.name, bounds_to_string(bounds))
+        }
+    }
+}
+
+fn term_to_string(term: &Term) -> String {
+    match term {
+        Term::Type(ty) => type_to_string(ty),
+        Term::Constant(constant) => constant.expr.clone(),
+    }
+}
+
+pub(crate) fn bounds_to_string(bounds: &[GenericBound]) -> String {
+    bounds
+        .iter()
+        .map(|bound| match bound {
+            GenericBound::TraitBound {
+                trait_,
+                generic_params,
+                modifier,
+            } => {
+                let modifier = match modifier {
+                    TraitBoundModifier::None => "",
+                    TraitBoundModifier::Maybe => "?",
+                    TraitBoundModifier::MaybeConst => "~const ",
+                };
+                format!(
+                    "{}{modifier}{}",
+                    higher_ranked(generic_params),
+                    path_to_string(trait_)
+                )
+            }
+            GenericBound::Outlives(lifetime) => lifetime.clone(),
+            GenericBound::Use(_) => "use<..>".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// Renders `for<'a>` binders
+fn higher_ranked(params: &[GenericParamDef]) -> String {
+    if params.is_empty() {
+        String::new()
+    } else {
+        let names: Vec<&str> = params.iter().map(|param| param.name.as_str()).collect();
+        format!("for<{}> ", names.join(", "))
+    }
+}
+
+/// Renders generic parameters with their inline bounds, e.g. `<'a, T: Display, const N: usize>`.
+///
+/// Parameters introduced by `impl Trait` arguments are skipped as they show up in the arguments.
+pub(crate) fn generic_params_to_string(params: &[GenericParamDef]) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .filter_map(|param| match &param.kind {
+            GenericParamDefKind::Lifetime { outlives } if outlives.is_empty() => {
+                Some(param.name.clone())
+            }
+            GenericParamDefKind::Lifetime { outlives } => {
+                Some(format!("{}: {}", param.name, outlives.join(" + ")))
+            }
+            GenericParamDefKind::Type {
+                is_synthetic: true, ..
+            } => None,
+            GenericParamDefKind::Type {
+                bounds, default, ..
+            } => {
+                let mut param = param.name.clone();
+                if !bounds.is_empty() {
+                    param = format!("{param}: {}", bounds_to_string(bounds));
+                }
+                if let Some(default) = default {
+                    param = format!("{param} = {}", type_to_string(default));
+                }
+                Some(param)
+            }
+            GenericParamDefKind::Const { type_, default } => {
+                let mut param = format!("const {}: {}", param.name, type_to_string(type_));
+                if let Some(default) = default {
+                    param = format!("{param} = {default}");
+                }
+                Some(param)
+            }
+        })
+        .collect();
+
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", params.join(", "))
+    }
+}
+
+/// Renders the where clause of `generics` including the leading ` where `, or an empty string.
+pub(crate) fn where_clause_to_string(generics: &Generics) -> String {
+    let predicates: Vec<String> = generics
+        .where_predicates
+        .iter()
+        .map(|predicate| match predicate {
+            WherePredicate::BoundPredicate {
+                type_,
+                bounds,
+                generic_params,
+            } => format!(
+                "{}{}: {}",
+                higher_ranked(generic_params),
+                type_to_string(type_),
+                bounds_to_string(bounds)
+            ),
+            WherePredicate::LifetimePredicate { lifetime, outlives } => {
+                format!("{lifetime}: {}", outlives.join(" + "))
+            }
+            WherePredicate::EqPredicate { lhs, rhs } => {
+                format!("{} == {}", type_to_string(lhs), term_to_string(rhs))
+            }
+        })
+        .collect();
+
+    if predicates.is_empty() {
+        String::new()
+    } else {
+        format!(" where {}", predicates.join(", "))
+    }
+}
+
+/// Renders an impl header, e.g. `impl<T: Display> ToString for T`.
+///
+/// Blanket impls are shown with their generic self type, not the type they were
+/// copied to.
+pub(crate) fn impl_header(impl_block: &Impl) -> String {
+    let unsafety = if impl_block.is_unsafe { "unsafe " } else { "" };
+    let generics = generic_params_to_string(&impl_block.generics.params);
+    let for_type = type_to_string(impl_block.blanket_impl.as_ref().unwrap_or(&impl_block.for_));
+    let where_clause = where_clause_to_string(&impl_block.generics);
+
+    match &impl_block.trait_ {
+        Some(trait_) => {
+            let negative = if impl_block.is_negative { "!" } else { "" };
+            format!(
+                "{unsafety}impl{generics} {negative}{} for {for_type}{where_clause}",
+                path_to_string(trait_)
+            )
+        }
+        None => format!("{unsafety}impl{generics} {for_type}{where_clause}"),
+    }
+}
+
+impl Item {
+    /// Renders the declaration of the item without its body, e.g. `fn parse(input: &str) -> u32`.
+    ///
+    /// Types and traits are rendered with their generics and bounds, fields and
+    /// constants with their types. Visibility and attributes are left out.
+    ///
+    /// # Returns
+    ///
+    /// The signature, `None` for items without one like modules, re-exports and macros.
+    pub fn signature(&self) -> Option<String> {
+        let name = &self.name;
+        let signature = match &self.inner {
+            ItemEnum::Function(function) => {
+                let header = &function.header;
+                let mut inputs: Vec<String> = function
+                    .sig
+                    .inputs
+                    .iter()
+                    .map(|(name, ty)| match (name.as_str(), ty) {
+                        ("self", Type::Generic(generic)) if generic == "Self" => "self".to_string(),
+                        ("self", Type::BorrowedRef { type_, .. })
+                            if matches!(type_.as_ref(), Type::Generic(generic) if generic == "Self") =>
+                        {
+                            type_to_string(ty).replace("Self", "self")
+                        }
+                        _ => format!("{name}: {}", type_to_string(ty)),
+                    })
+                    .collect();
+                if function.sig.is_c_variadic {
+                    inputs.push("...".to_string());
+                }
+                let output = function
+                    .sig
+                    .output
+                    .as_ref()
+                    .map(|ty| format!(" -> {}", type_to_string(ty)))
+                    .unwrap_or_default();
+                format!(
+                    "{}{}{}{}fn {name}{}({}){output}{}",
+                    if header.is_const { "const " } else { "" },
+                    if header.is_async { "async " } else { "" },
+                    if header.is_unsafe { "unsafe " } else { "" },
+                    abi_to_string(&header.abi),
+                    generic_params_to_string(&function.generics.params),
+                    inputs.join(", "),
+                    where_clause_to_string(&function.generics)
+                )
+            }
+            ItemEnum::Struct(s) => format!("struct {name}{}", generics_to_string(&s.generics)),
+            ItemEnum::Enum(e) => format!("enum {name}{}", generics_to_string(&e.generics)),
+            ItemEnum::Union(u) => format!("union {name}{}", generics_to_string(&u.generics)),
+            ItemEnum::Variant(variant) => match variant.kind {
+                VariantKind::Plain => name.clone(),
+                VariantKind::Tuple(_) => format!("{name}(..)"),
+                VariantKind::Struct { .. } => format!("{name} {{ .. }}"),
+            },
+            ItemEnum::StructField(ty) => format!("{name}: {}", type_to_string(ty)),
+            ItemEnum::Trait(t) => {
+                let bounds = if t.bounds.is_empty() {
+                    String::new()
+                } else {
+                    format!(": {}", bounds_to_string(&t.bounds))
+                };
+                format!(
+                    "{}{}trait {name}{}{bounds}{}",
+                    if t.is_unsafe { "unsafe " } else { "" },
+                    if t.is_auto { "auto " } else { "" },
+                    generic_params_to_string(&t.generics.params),
+                    where_clause_to_string(&t.generics)
+                )
+            }
+            ItemEnum::TraitAlias(alias) => format!(
+                "trait {name}{} = {}{}",
+                generic_params_to_string(&alias.generics.params),
+                bounds_to_string(&alias.params),
+                where_clause_to_string(&alias.generics)
+            ),
+            ItemEnum::TypeAlias(alias) => format!(
+                "type {name}{}{} = {}",
+                generic_params_to_string(&alias.generics.params),
+                where_clause_to_string(&alias.generics),
+                type_to_string(&alias.type_)
+            ),
+            ItemEnum::Constant { type_, .. } | ItemEnum::AssocConst { type_, .. } => {
+                format!("const {name}: {}", type_to_string(type_))
+            }
+            ItemEnum::Static(s) => format!(
+                "{}static {}{name}: {}",
+                if s.is_unsafe { "unsafe " } else { "" },
+                if s.is_mutable { "mut " } else { "" },
+                type_to_string(&s.type_)
+            ),
+            ItemEnum::AssocType {
+                generics,
+                bounds,
+                type_,
+            } => {
+                let mut signature =
+                    format!("type {name}{}", generic_params_to_string(&generics.params));
+                if !bounds.is_empty() {
+                    signature = format!("{signature}: {}", bounds_to_string(bounds));
+                }
+                signature.push_str(&where_clause_to_string(generics));
+                if let Some(ty) = type_ {
+                    signature = format!("{signature} = {}", type_to_string(ty));
+                }
+                signature
+            }
+            _ => return None,
+        };
+        Some(signature)
+    }
+}
+
+/// Renders generic parameters followed by the where clause
+fn generics_to_string(generics: &Generics) -> String {
+    format!(
+        "{}{}",
+        generic_params_to_string(&generics.params),
+        where_clause_to_string(generics)
+    )
+}
+
+/// Renders the `extern "abi" ` of a function header, empty for the Rust ABI
+fn abi_to_string(abi: &Abi) -> String {
+    let (name, unwind) = match abi {
+        Abi::Rust => return String::new(),
+        Abi::C { unwind } => ("C", unwind),
+        Abi::Cdecl { unwind } => ("cdecl", unwind),
+        Abi::Stdcall { unwind } => ("stdcall", unwind),
+        Abi::Fastcall { unwind } => ("fastcall", unwind),
+        Abi::Aapcs { unwind } => ("aapcs", unwind),
+        Abi::Win64 { unwind } => ("win64", unwind),
+        Abi::SysV64 { unwind } => ("sysv64", unwind),
+        Abi::System { unwind } => ("system", unwind),
+        Abi::Other(name) => return format!("extern \"{name}\" "),
+    };
+    let unwind = if *unwind { "-unwind" } else { "" };
+    format!("extern \"{name}{unwind}\" ")
+}
@@ -17,6 +17,11 @@
 //! - **`default`** - Core functionality for loading and parsing JSON files
 //! - **`decompress`** - Adds support for decompressing zstd-compressed files
 //! - **`fetch`** - Enables fetching compressed documentation directly from docs.rs
+//! - **`nucleo`** - Adds the [`NucleoRanker`] for nucleo-style fuzzy matching
+//! - **`serde`** - Derives `Serialize` and `Deserialize` for [`Item`] and the other public data types
+//! - **`tui`** - Adds the interactive `tui` command to the `docsrs` binary
+//! - **`serve`** - Adds the `serve` command to the `docsrs` binary, an HTTP search API and page
+//! - **`lsp`** - Builds the `docsrs-lsp` language server serving the docs of a project's dependencies
 //!
 //! ## Type-State Pipeline
 //!
@@ -46,6 +51,22 @@
 //! - **Case-insensitive**: `"hashmap"` matches `HashMap`
 //! - **Methods and functions**: `"tokio::spawn"` finds the spawn function
 //! - **Ranked results**: Results are sorted by relevance score
+//! - **Custom ranking**: Pass any [`Ranker`] to [`Doc::search_with`] to tune relevance,
+//!   e.g. [`KindWeights`] to rank types above methods or [`Penalize`] to push down deprecated items
+//!
+//! To walk the whole crate instead, [`Doc::items`] and [`Doc::iter_by_kind`] iterate
+//! over the items in a stable order and [`Doc::lookup`] finds the items at an exact path.
+//!
+//! Trait implementations can be browsed both ways: [`Doc::implementors_of`] lists the
+//! impls of a trait and [`Doc::traits_implemented_by`] the impls of a type.
+//! [`Doc::methods_of`] lists every method callable on a type, including trait methods
+//! and methods reachable through `Deref`, which are also searchable under the type's path.
+//! [`Doc::diff`] compares the public APIs of two versions of a crate and classifies
+//! the changes as breaking or minor. [`Doc::export`] writes the docs as Markdown
+//! or plain-text pages, one per module and type. [`Doc::coverage`] reports how much
+//! of the public API is documented, along with missing doc sections and broken links.
+//! [`Item::render_docs`] renders docs for the terminal, with wrapped paragraphs,
+//! highlighted code and colors following [`TerminalOptions`].
 //!
 //! ## Item Information
 //!
@@ -114,22 +135,24 @@
 //! ### Analyzing Documentation Coverage
 //!
 //! ```rust,ignore
-//! use docsrs::Doc;
+//! use docsrs::{Doc, DocLint};
 //!
 //! fn analyze_coverage(crate_name: &str) -> Result<(), Box<dyn std::error::Error>> {
-//!     let doc = Doc::from_docs(crate_name, "latest")?
+//!     let report = Doc::from_docs(crate_name, "latest")?
 //!         .fetch()?
 //!         .decompress()?
 //!         .parse()?
-//!         .build_search_index();
+//!         .coverage();
+//!
+//!     println!("Total items: {}", report.total.total);
+//!     println!("Documented: {}", report.total.documented);
+//!     println!("Coverage: {:.1}%", report.total.percentage());
+//!     for issue in &report.issues {
+//!         if let DocLint::MissingSection { section } = &issue.lint {
+//!             println!("{} lacks a `# {section}` section", issue.path.join("::"));
+//!         }
+//!     }
 //!
-//!     let all_items = doc.search("", None).unwrap_or_default();
-//!     let documented = all_items.iter().filter(|item| item.docs.is_some()).count();
-//!     
-//!     println!("Total items: {}", all_items.len());
-//!     println!("Documented: {}", documented);
-//!     println!("Coverage: {:.1}%", (documented as f64 / all_items.len() as f64) * 100.0);
-//!     
 //!     Ok(())
 //! }
 //! ```
@@ -147,8 +170,19 @@ mod error;
 
 pub use doc::Doc;
 
-pub use doc::Item;
-pub use doc::{Indexed, Parsed, RawJson};
+pub use doc::{
+    ApiChange, ApiDiff, ChangeKind, Coverage, CoverageReport, DocIssue, DocLint, Example,
+    ExportFormat, ImplKind, Implementation, IndexOptions, Item, Method, MethodOrigin,
+    PathPreference, SearchHit, Severity,
+};
+pub use doc::{Indexed, MarkdownLine, MarkdownSpan, ModuleTree, Parsed, RawJson, TerminalOptions};
+
+pub use doc::{
+    Candidate, KindWeights, LastSegmentBoost, Penalize, PrefixBoost, Ranker, SkimRanker,
+};
+
+#[cfg(feature = "nucleo")]
+pub use doc::NucleoRanker;
 
 #[cfg(feature = "fetch")]
 pub use doc::Remote;
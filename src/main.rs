@@ -1,15 +1,46 @@
-use clap::Parser;
-use docsrs::{Doc, Error};
+use cache::Cache;
+use clap::{Parser, Subcommand, ValueEnum};
+use docsrs::{
+    ApiChange, ApiDiff, ChangeKind, Coverage, CoverageReport, Doc, DocIssue, DocLint, Error,
+    Example, ImplKind, Implementation, Indexed, Item, ModuleTree, PathPreference, SearchHit,
+    Severity, TerminalOptions,
+};
+use rustdoc_types::ItemKind;
+use serde_json::{Value, json};
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+
+mod cache;
+#[cfg(unix)]
+mod daemon;
+mod mcp;
+#[cfg(feature = "serve")]
+mod serve;
+#[cfg(feature = "tui")]
+mod tui;
 
 /// A fast, fuzzy-search for rust-docs.
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true,
+    after_help = "To search a crate named like a command, e.g. `tree`, put `--` before it: docsrs -- tree Node"
+)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The crate to search in
-    crate_name: String,
+    #[arg(required = true)]
+    crate_name: Option<String>,
 
     /// The search query
-    query: String,
+    #[arg(required = true)]
+    query: Option<String>,
 
     /// The version of the crate to search in
     #[arg(short = 'v', long = "crate-version", default_value = "latest", value_name = "VERSION")]
@@ -18,37 +49,971 @@ struct Args {
     /// The number of search results to return
     #[arg(short, long, default_value_t = 10)]
     n: usize,
+
+    /// Which path to print for results reachable by several paths [default: the matched one]
+    #[arg(short, long, value_enum)]
+    path: Option<PathArg>,
+
+    /// The output format
+    #[arg(short, long, value_enum, default_value_t = Format::Text, global = true)]
+    format: Format,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List the implementations of the best matching trait
+    Impls {
+        /// The crate to search in
+        crate_name: String,
+
+        /// The trait to search for
+        query: String,
+
+        /// The version of the crate to search in
+        #[arg(
+            short = 'v',
+            long = "crate-version",
+            default_value = "latest",
+            value_name = "VERSION"
+        )]
+        crate_version: String,
+    },
+    /// List the traits implemented by the best matching type
+    Traits {
+        /// The crate to search in
+        crate_name: String,
+
+        /// The type to search for
+        query: String,
+
+        /// The version of the crate to search in
+        #[arg(
+            short = 'v',
+            long = "crate-version",
+            default_value = "latest",
+            value_name = "VERSION"
+        )]
+        crate_version: String,
+    },
+    /// Print the full page of the items at an exact path
+    Show {
+        /// The crate of the item
+        crate_name: String,
+
+        /// The path of the item, e.g. `tokio::sync::Mutex` or `tokio::sync::Mutex::lock`
+        path: String,
+
+        /// The version of the crate
+        #[arg(
+            short = 'v',
+            long = "crate-version",
+            default_value = "latest",
+            value_name = "VERSION"
+        )]
+        crate_version: String,
+    },
+    /// Print the code examples from the docs of the best matching items
+    Examples {
+        /// The crate to search in
+        crate_name: String,
+
+        /// The search query
+        query: String,
+
+        /// The version of the crate to search in
+        #[arg(
+            short = 'v',
+            long = "crate-version",
+            default_value = "latest",
+            value_name = "VERSION"
+        )]
+        crate_version: String,
+
+        /// The number of search results to look for examples in
+        #[arg(short, long, default_value_t = 10)]
+        n: usize,
+
+        /// Include the hidden lines (`# ...`) rustdoc compiles the examples with
+        #[arg(long)]
+        hidden: bool,
+    },
+    /// Print the module hierarchy of a crate
+    Tree {
+        /// The crate to print, or a module to start at like `tokio::sync`, whose
+        /// first segment names the crate
+        crate_name: String,
+
+        /// The module to start at, e.g. `tokio::sync` [default: the crate root]
+        path: Option<String>,
+
+        /// The version of the crate
+        #[arg(
+            short = 'v',
+            long = "crate-version",
+            default_value = "latest",
+            value_name = "VERSION"
+        )]
+        crate_version: String,
+    },
+    /// Compare the public API of two versions of a crate
+    Diff {
+        /// The crate to compare
+        crate_name: String,
+
+        /// The old version, e.g. `1.0.190`
+        old_version: String,
+
+        /// The new version, e.g. `1.0.200`
+        new_version: String,
+    },
+    /// Report how well the public API of a crate is documented
+    Coverage {
+        /// The crate to check
+        crate_name: String,
+
+        /// The version of the crate
+        #[arg(
+            short = 'v',
+            long = "crate-version",
+            default_value = "latest",
+            value_name = "VERSION"
+        )]
+        crate_version: String,
+    },
+    /// Serve crate docs to coding assistants, as a Model Context Protocol server on stdio
+    Mcp {
+        /// Rustdoc JSON files to serve instead of fetching their crates, named after
+        /// the crate like `target/doc/serde_json.json`
+        #[arg(long = "docs", value_name = "PATH")]
+        docs: Vec<PathBuf>,
+    },
+    /// Keep crates loaded in the background for the other commands, which use it while it runs
+    #[cfg(unix)]
+    Daemon {
+        /// The budget for the loaded crates in MiB of their rustdoc JSON, the parsed
+        /// and indexed crates take a few times as much memory
+        #[arg(long, default_value_t = 1024, value_name = "MIB")]
+        max_json_mib: usize,
+    },
+    /// Serve a search API and page over HTTP, keeping the indexes in memory
+    #[cfg(feature = "serve")]
+    Serve {
+        /// The crates to serve, as `name` or `name@version`, e.g. `tokio@1.38.0`
+        #[arg(required = true)]
+        crates: Vec<String>,
+
+        /// The address to listen on, e.g. `0.0.0.0` to serve other machines
+        #[arg(long, default_value = "127.0.0.1", value_name = "ADDR")]
+        bind: std::net::IpAddr,
+
+        /// The port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+
+        /// Rustdoc JSON files to serve instead of fetching their crates, named after
+        /// the crate like `target/doc/serde_json.json`
+        #[arg(long = "docs", value_name = "PATH")]
+        docs: Vec<PathBuf>,
+    },
+    /// Search a crate interactively, with a preview of the selected item
+    #[cfg(feature = "tui")]
+    Tui {
+        /// The crate to search in
+        crate_name: String,
+
+        /// The version of the crate to search in
+        #[arg(
+            short = 'v',
+            long = "crate-version",
+            default_value = "latest",
+            value_name = "VERSION"
+        )]
+        crate_version: String,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum PathArg {
+    /// The shortest path, usually a re-export
+    Shortest,
+    /// The path the item is defined at
+    Definition,
+    /// The shortest public path, skipping private modules and hidden items
+    Canonical,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    /// Human readable text
+    Text,
+    /// A JSON array of records
+    Json,
+    /// One JSON record per line
+    Jsonl,
+}
+
+impl From<PathArg> for PathPreference {
+    fn from(arg: PathArg) -> Self {
+        match arg {
+            PathArg::Shortest => PathPreference::Shortest,
+            PathArg::Definition => PathPreference::Definition,
+            PathArg::Canonical => PathPreference::Canonical,
+        }
+    }
+}
+
+/// Where and how a command prints its results
+struct Output<'a> {
+    format: Format,
+    /// Whether to print ANSI colors, see [`use_color`]
+    color: bool,
+    /// The width to wrap docs at, see [`TerminalOptions::width`]
+    width: Option<usize>,
+    out: &'a mut dyn Write,
+    /// For messages that would break JSON output
+    err: &'a mut dyn Write,
+}
+
+impl Output<'_> {
+    /// How to render docs, colored if the output is
+    fn terminal(&self) -> TerminalOptions {
+        TerminalOptions {
+            width: self.width,
+            color: self.color,
+        }
+    }
 }
 
 fn main() -> Result<(), Error> {
     let args = Args::parse();
 
-    let doc = Doc::from_docs(&args.crate_name, &args.crate_version)?
-        .fetch()?
-        .decompress()?
-        .parse()?
-        .build_search_index();
+    match &args.command {
+        Some(Command::Mcp { docs }) => return mcp::run(docs.clone()),
+        #[cfg(feature = "serve")]
+        Some(Command::Serve {
+            crates,
+            bind,
+            port,
+            docs,
+        }) => {
+            return serve::run(*bind, *port, crates, docs.clone());
+        }
+        #[cfg(feature = "tui")]
+        Some(Command::Tui {
+            crate_name,
+            crate_version,
+        }) => {
+            let loaded = Cache::unbounded().get(crate_name, crate_version)?;
+            return tui::run(crate_name, loaded.indexed());
+        }
+        #[cfg(unix)]
+        Some(Command::Daemon { max_json_mib }) => {
+            let budget = max_json_mib.saturating_mul(1024 * 1024);
+            return daemon::run(&daemon::socket_path(), budget);
+        }
+        _ => {}
+    }
 
-    if let Some(results) = doc.search(&args.query, Some(args.n)) {
+    let color = use_color();
+    let width = TerminalOptions::from_env().width;
+    #[cfg(unix)]
+    match daemon::forward(&daemon::socket_path(), color, width) {
+        Ok(true) => return Ok(()),
+        Ok(false) => {}
+        Err(e) => log::debug!("not using the daemon: {e}"),
+    }
+
+    let mut output = Output {
+        format: args.format,
+        color,
+        width,
+        out: &mut std::io::stdout().lock(),
+        err: &mut std::io::stderr(),
+    };
+    execute(args, &mut Cache::unbounded(), &mut output)
+}
+
+/// Runs a command printing results from the docs of crates, in this process or in the daemon
+fn execute(args: Args, cache: &mut Cache, output: &mut Output) -> Result<(), Error> {
+    match args.command {
+        Some(Command::Impls {
+            crate_name,
+            query,
+            crate_version,
+        }) => {
+            let loaded = cache.get(&crate_name, &crate_version)?;
+            let doc = loaded.indexed();
+            match find(doc, &query, &[ItemKind::Trait]) {
+                Some(item) => print_implementations(
+                    output,
+                    &format!("Implementors of {}", item.path.join("::")),
+                    &doc.implementors_of(item),
+                )?,
+                None => not_found(
+                    output,
+                    &format!("No trait found for query `{query}` in crate `{crate_name}`"),
+                )?,
+            }
+        }
+        Some(Command::Traits {
+            crate_name,
+            query,
+            crate_version,
+        }) => {
+            let loaded = cache.get(&crate_name, &crate_version)?;
+            let doc = loaded.indexed();
+            let kinds = [
+                ItemKind::Struct,
+                ItemKind::Enum,
+                ItemKind::Union,
+                ItemKind::Primitive,
+            ];
+            match find(doc, &query, &kinds) {
+                Some(item) => print_implementations(
+                    output,
+                    &format!("Implementations on {}", item.path.join("::")),
+                    &doc.traits_implemented_by(item),
+                )?,
+                None => not_found(
+                    output,
+                    &format!("No type found for query `{query}` in crate `{crate_name}`"),
+                )?,
+            }
+        }
+        Some(Command::Show {
+            crate_name,
+            path,
+            crate_version,
+        }) => {
+            let loaded = cache.get(&crate_name, &crate_version)?;
+            let doc = loaded.indexed();
+            // a path can name several items, e.g. a field and a method
+            let items = doc.lookup(&path);
+            if items.is_empty() {
+                not_found(output, &format!("No item `{path}` in crate `{crate_name}`"))?;
+            } else if output.format == Format::Text {
+                let pages: Vec<String> = items
+                    .iter()
+                    .map(|item| output.terminal().render(&doc.page(item)))
+                    .collect();
+                write!(output.out, "{}", pages.join("\n"))?;
+            } else {
+                let records = items
+                    .into_iter()
+                    .map(|item| {
+                        let mut record = item_json(item)?;
+                        record["page"] = doc.page(item).into();
+                        Ok(record)
+                    })
+                    .collect::<Result<_, Error>>()?;
+                emit(output, records)?;
+            }
+        }
+        Some(Command::Examples {
+            crate_name,
+            query,
+            crate_version,
+            n,
+            hidden,
+        }) => {
+            let loaded = cache.get(&crate_name, &crate_version)?;
+            let hits = loaded
+                .indexed()
+                .search_detailed(&query, Some(n))
+                .unwrap_or_default();
+            let examples: Vec<(&SearchHit, Example)> = hits
+                .iter()
+                .flat_map(|hit| {
+                    hit.item
+                        .examples()
+                        .into_iter()
+                        .map(move |example| (hit, example))
+                })
+                .collect();
+            if examples.is_empty() {
+                not_found(
+                    output,
+                    &format!("No examples found for query `{query}` in crate `{crate_name}`"),
+                )?;
+            } else {
+                print_examples(output, &examples, hidden)?;
+            }
+        }
+        Some(Command::Tree {
+            crate_name,
+            path,
+            crate_version,
+        }) => {
+            // `tree tokio::sync` is short for `tree tokio tokio::sync`
+            let (crate_name, path) = match path {
+                None if crate_name.contains("::") => {
+                    let name = crate_name.split("::").next().unwrap_or_default();
+                    (name.to_string(), Some(crate_name))
+                }
+                path => (crate_name, path),
+            };
+            let loaded = cache.get(&crate_name, &crate_version)?;
+            let tree = loaded.parsed.module_tree();
+            let start = match &path {
+                Some(path) => tree.find(path),
+                None => Some(tree.root()),
+            };
+            match start {
+                Some(start) => print_tree(output, &tree, start)?,
+                None => not_found(
+                    output,
+                    &format!(
+                        "No module `{}` in crate `{crate_name}`",
+                        path.unwrap_or_default()
+                    ),
+                )?,
+            }
+        }
+        Some(Command::Diff {
+            crate_name,
+            old_version,
+            new_version,
+        }) => {
+            let old = cache.get(&crate_name, &old_version)?;
+            let new = cache.get(&crate_name, &new_version)?;
+            let diff = old.indexed().diff(new.indexed());
+            if output.format == Format::Text {
+                writeln!(output.out, "{crate_name} {old_version} -> {new_version}")?;
+                print_diff(output.out, &diff)?;
+            } else {
+                emit(output, diff.changes.iter().map(change_json).collect())?;
+            }
+        }
+        Some(Command::Coverage {
+            crate_name,
+            crate_version,
+        }) => {
+            let loaded = cache.get(&crate_name, &crate_version)?;
+            let report = loaded.parsed.coverage();
+            if output.format == Format::Text {
+                print_coverage(output.out, &crate_name, &report)?;
+            } else {
+                emit(output, vec![coverage_json(&crate_name, &report)])?;
+            }
+        }
+        None => {
+            // clap makes sure both are present without a subcommand
+            let (Some(crate_name), Some(query)) = (args.crate_name, args.query) else {
+                unreachable!()
+            };
+            let loaded = cache.get(&crate_name, &args.crate_version)?;
+            search(
+                output,
+                loaded.indexed(),
+                &crate_name,
+                &query,
+                args.n,
+                args.path,
+            )?;
+        }
+        Some(command) => {
+            let message = format!("{command:?} doesn't print results from docs");
+            return Err(std::io::Error::other(message).into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints examples under the path of the search result they are from
+fn print_examples(
+    output: &mut Output,
+    examples: &[(&SearchHit, Example)],
+    hidden: bool,
+) -> Result<(), Error> {
+    let code = |example: &Example| {
+        if hidden {
+            example.full_code()
+        } else {
+            example.code()
+        }
+    };
+
+    if output.format != Format::Text {
+        let records = examples
+            .iter()
+            .map(|(hit, example)| {
+                json!({
+                    "path": hit.matched_key,
+                    "section": example.section,
+                    "ignore": example.ignore,
+                    "no_run": example.no_run,
+                    "should_panic": example.should_panic,
+                    "compile_fail": example.compile_fail,
+                    "edition": example.edition,
+                    "code": code(example),
+                })
+            })
+            .collect();
+        return emit(output, records);
+    }
+
+    let terminal = output.terminal();
+    for (i, (hit, example)) in examples.iter().enumerate() {
+        let flags: Vec<String> = [
+            (example.ignore, "ignore"),
+            (example.no_run, "no_run"),
+            (example.should_panic, "should_panic"),
+            (example.compile_fail, "compile_fail"),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, flag)| flag.to_string())
+        .chain(
+            example
+                .edition
+                .iter()
+                .map(|edition| format!("edition{edition}")),
+        )
+        .collect();
+
+        let mut title = format!("## `{}`", hit.matched_key);
+        if !flags.is_empty() {
+            title.push_str(&format!(" ({})", flags.join(", ")));
+        }
+        if i > 0 {
+            writeln!(output.out)?;
+        }
+        writeln!(output.out, "{}", terminal.render(&title))?;
+        write!(output.out, "{}", terminal.render_code(&code(example)))?;
+    }
+    Ok(())
+}
+
+/// Prints the items below `start` indented by their depth, with their kinds
+fn print_tree(
+    output: &mut Output,
+    tree: &ModuleTree,
+    start: &rustdoc_types::Id,
+) -> Result<(), Error> {
+    let walked = tree.walk_from(start);
+    if output.format != Format::Text {
+        let records = walked
+            .into_iter()
+            .map(|(depth, id)| {
+                json!({
+                    "depth": depth,
+                    "name": tree.name(id),
+                    "kind": tree.kind(id),
+                })
+            })
+            .collect();
+        return emit(output, records);
+    }
+
+    for (depth, id) in walked {
+        let name = tree.name(id).unwrap_or("?");
+        let kind = kind_keyword(tree.kind(id));
+        writeln!(output.out, "{}{kind} {name}", "  ".repeat(depth))?;
+    }
+    Ok(())
+}
+
+/// Prints records as a pretty JSON array or as one compact JSON record per line
+fn emit(output: &mut Output, records: Vec<Value>) -> Result<(), Error> {
+    if output.format == Format::Jsonl {
+        for record in records {
+            writeln!(output.out, "{}", serde_json::to_string(&record)?)?;
+        }
+    } else {
+        writeln!(output.out, "{}", serde_json::to_string_pretty(&records)?)?;
+    }
+    Ok(())
+}
+
+/// Reports that nothing was found, for JSON output on stderr followed by an empty list
+fn not_found(output: &mut Output, message: &str) -> Result<(), Error> {
+    if output.format == Format::Text {
+        writeln!(output.out, "{message}")?;
+        return Ok(());
+    }
+
+    writeln!(output.err, "{message}")?;
+    emit(output, Vec::new())
+}
+
+/// The keyword an item of the given kind is declared with
+fn kind_keyword(kind: Option<ItemKind>) -> &'static str {
+    match kind {
+        Some(ItemKind::Module) => "mod",
+        Some(ItemKind::Struct) => "struct",
+        Some(ItemKind::Enum) => "enum",
+        Some(ItemKind::Variant) => "variant",
+        Some(ItemKind::Union) => "union",
+        Some(ItemKind::Function) => "fn",
+        Some(ItemKind::Trait) => "trait",
+        Some(ItemKind::TraitAlias) => "trait alias",
+        Some(ItemKind::TypeAlias) => "type",
+        Some(ItemKind::Constant) => "const",
+        Some(ItemKind::Static) => "static",
+        Some(ItemKind::Macro) => "macro",
+        Some(ItemKind::Primitive) => "primitive",
+        Some(ItemKind::ExternCrate) => "extern crate",
+        Some(ItemKind::ProcAttribute) => "attribute macro",
+        Some(ItemKind::ProcDerive) => "derive macro",
+        Some(ItemKind::StructField) => "field",
+        Some(ItemKind::AssocConst) => "const",
+        Some(ItemKind::AssocType) => "type",
+        _ => "item",
+    }
+}
+
+/// Returns the best match for `query` that is of one of the given kinds
+fn find<'a>(doc: &'a Doc<Indexed>, query: &str, kinds: &[ItemKind]) -> Option<&'a Item> {
+    doc.search(query, None)?
+        .into_iter()
+        .find(|item| item.kind.is_some_and(|kind| kinds.contains(&kind)))
+}
+
+/// Prints implementations grouped by their kind
+fn print_implementations(
+    output: &mut Output,
+    title: &str,
+    implementations: &[Implementation],
+) -> Result<(), Error> {
+    if output.format != Format::Text {
+        let records = implementations
+            .iter()
+            .map(|implementation| {
+                json!({
+                    "kind": impl_kind_name(implementation.kind),
+                    "trait": implementation.trait_path.as_ref().map(|path| path.join("::")),
+                    "for": implementation.for_type,
+                    "header": implementation.header,
+                })
+            })
+            .collect();
+        return emit(output, records);
+    }
+
+    writeln!(output.out, "{title}")?;
+    if implementations.is_empty() {
+        writeln!(output.out, "\nNone")?;
+        return Ok(());
+    }
+
+    let mut kind = None;
+    for implementation in implementations {
+        if kind != Some(implementation.kind) {
+            kind = Some(implementation.kind);
+            let heading = match implementation.kind {
+                ImplKind::Inherent => "Inherent implementations",
+                ImplKind::Trait => "Trait implementations",
+                ImplKind::Auto => "Auto trait implementations",
+                ImplKind::Synthetic => "Synthetic auto trait implementations",
+                ImplKind::Blanket => "Blanket implementations",
+            };
+            writeln!(output.out, "\n{heading}:")?;
+        }
+        writeln!(output.out, "  {}", implementation.header)?;
+    }
+    Ok(())
+}
+
+fn impl_kind_name(kind: ImplKind) -> &'static str {
+    match kind {
+        ImplKind::Inherent => "inherent",
+        ImplKind::Trait => "trait",
+        ImplKind::Auto => "auto",
+        ImplKind::Synthetic => "synthetic",
+        ImplKind::Blanket => "blanket",
+    }
+}
+
+/// Prints the breaking and minor changes of a diff
+fn print_diff(out: &mut dyn Write, diff: &ApiDiff) -> std::io::Result<()> {
+    if diff.is_empty() {
+        return writeln!(out, "\nNo changes to the public API");
+    }
+
+    for (heading, changes) in [
+        ("Breaking changes", diff.breaking().collect::<Vec<_>>()),
+        ("Minor changes", diff.minor().collect()),
+    ] {
+        if !changes.is_empty() {
+            writeln!(out, "\n{heading}:")?;
+            for change in changes {
+                print_change(out, change)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_change(out: &mut dyn Write, change: &ApiChange) -> std::io::Result<()> {
+    let item = format!("{} {}", kind_keyword(change.kind), change.path.join("::"));
+    match &change.change {
+        ChangeKind::Added => writeln!(out, "  added {item}"),
+        ChangeKind::Removed => writeln!(out, "  removed {item}"),
+        ChangeKind::SignatureChanged { old, new } => {
+            writeln!(out, "  changed {item}\n    - {old}\n    + {new}")
+        }
+        ChangeKind::ImplAdded { header } => writeln!(out, "  added `{header}` to {item}"),
+        ChangeKind::ImplRemoved { header } => writeln!(out, "  removed `{header}` from {item}"),
+        ChangeKind::Deprecated { note: Some(note) } => writeln!(out, "  deprecated {item}: {note}"),
+        ChangeKind::Deprecated { note: None } => writeln!(out, "  deprecated {item}"),
+    }
+}
+
+/// The JSON record of a change, with the fields of the change next to `change`
+fn change_json(change: &ApiChange) -> Value {
+    let (name, details) = match &change.change {
+        ChangeKind::Added => ("added", json!({})),
+        ChangeKind::Removed => ("removed", json!({})),
+        ChangeKind::SignatureChanged { old, new } => {
+            ("signature_changed", json!({"old": old, "new": new}))
+        }
+        ChangeKind::ImplAdded { header } => ("impl_added", json!({"header": header})),
+        ChangeKind::ImplRemoved { header } => ("impl_removed", json!({"header": header})),
+        ChangeKind::Deprecated { note } => ("deprecated", json!({"note": note})),
+    };
+    let severity = match change.severity {
+        Severity::Breaking => "breaking",
+        Severity::Minor => "minor",
+    };
+
+    let mut record = json!({
+        "path": change.path.join("::"),
+        "kind": change.kind,
+        "severity": severity,
+        "change": name,
+    });
+    if let (Value::Object(record), Value::Object(details)) = (&mut record, details) {
+        record.extend(details);
+    }
+    record
+}
+
+/// Prints the coverage per kind and module, then the issues
+fn print_coverage(
+    out: &mut dyn Write,
+    crate_name: &str,
+    report: &CoverageReport,
+) -> std::io::Result<()> {
+    let total = report.total;
+    writeln!(
+        out,
+        "{crate_name}: {}/{} public items documented ({:.1}%)",
+        total.documented,
+        total.total,
+        total.percentage()
+    )?;
+
+    let by_kind = report.by_kind.iter().map(|(kind, coverage)| {
+        let name = match kind {
+            ItemKind::AssocConst => "associated const",
+            ItemKind::AssocType => "associated type",
+            kind => kind_keyword(Some(*kind)),
+        };
+        (name.to_string(), coverage)
+    });
+    let by_module = report
+        .by_module
+        .iter()
+        .map(|(module, coverage)| (module.join("::"), coverage));
+    for (heading, rows) in [
+        ("By kind", by_kind.collect::<Vec<_>>()),
+        ("By module", by_module.collect()),
+    ] {
+        writeln!(out, "\n{heading}:")?;
+        let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        for (name, coverage) in rows {
+            let counts = format!("{}/{}", coverage.documented, coverage.total);
+            writeln!(
+                out,
+                "  {name:width$}  {counts:>9}  {:5.1}%",
+                coverage.percentage()
+            )?;
+        }
+    }
+
+    if !report.issues.is_empty() {
+        writeln!(out, "\nIssues:")?;
+        for issue in &report.issues {
+            print_issue(out, issue)?;
+        }
+    }
+    Ok(())
+}
+
+fn print_issue(out: &mut dyn Write, issue: &DocIssue) -> std::io::Result<()> {
+    let item = format!(
+        "{} {}",
+        kind_keyword(Some(issue.kind)),
+        issue.path.join("::")
+    );
+    match &issue.lint {
+        DocLint::Undocumented => writeln!(out, "  undocumented {item}"),
+        DocLint::MissingSection { section } => {
+            writeln!(out, "  missing `# {section}` section in {item}")
+        }
+        DocLint::BrokenLink { link } => writeln!(out, "  broken link `{link}` in {item}"),
+    }
+}
+
+/// The JSON record of a coverage report, with the fields of each lint next to `lint`
+fn coverage_json(crate_name: &str, report: &CoverageReport) -> Value {
+    let counts = |coverage: &Coverage| {
+        json!({
+            "documented": coverage.documented,
+            "total": coverage.total,
+            "percentage": coverage.percentage(),
+        })
+    };
+    let group = |key: &str, name: Value, coverage| {
+        let mut record = counts(coverage);
+        record[key] = name;
+        record
+    };
+    let issue_json = |issue: &DocIssue| {
+        let (lint, details) = match &issue.lint {
+            DocLint::Undocumented => ("undocumented", json!({})),
+            DocLint::MissingSection { section } => ("missing_section", json!({"section": section})),
+            DocLint::BrokenLink { link } => ("broken_link", json!({"link": link})),
+        };
+        let mut record = json!({
+            "path": issue.path.join("::"),
+            "kind": issue.kind,
+            "lint": lint,
+        });
+        if let (Value::Object(record), Value::Object(details)) = (&mut record, details) {
+            record.extend(details);
+        }
+        record
+    };
+
+    let mut record = counts(&report.total);
+    record["crate"] = crate_name.into();
+    record["by_kind"] = report
+        .by_kind
+        .iter()
+        .map(|(kind, coverage)| group("kind", json!(kind), coverage))
+        .collect();
+    record["by_module"] = report
+        .by_module
+        .iter()
+        .map(|(module, coverage)| group("module", module.join("::").into(), coverage))
+        .collect();
+    record["issues"] = report.issues.iter().map(issue_json).collect();
+    record
+}
+
+/// The JSON record of an item, the schema is documented in the README
+fn item_json(item: &Item) -> Result<Value, Error> {
+    Ok(json!({
+        "path": item.path_by(PathPreference::Canonical).join("::"),
+        "name": item.name,
+        "kind": item.kind,
+        "canonical_path": item.canonical_path.as_ref().map(|path| path.join("::")),
+        "version": item.crate_version,
+        "url": item.url()?.map(String::from),
+        "signature": item.signature(),
+        "deprecation": item.deprecation,
+        "docs": item.docs,
+    }))
+}
+
+/// The JSON record of a search result, the record of the item with the matched path and score
+fn hit_json(hit: &SearchHit, preference: Option<PathArg>) -> Result<Value, Error> {
+    let mut record = item_json(hit.item)?;
+    record["path"] = match preference {
+        Some(preference) => hit.item.path_by(preference.into()).join("::"),
+        None => hit.matched_key.to_string(),
+    }
+    .into();
+    record["score"] = hit.score.into();
+    Ok(record)
+}
+
+fn search(
+    output: &mut Output,
+    doc: &Doc<Indexed>,
+    crate_name: &str,
+    query: &str,
+    n: usize,
+    path: Option<PathArg>,
+) -> Result<(), Error> {
+    if output.format != Format::Text {
+        let Some(results) = doc.search_detailed(query, Some(n)) else {
+            return not_found(
+                output,
+                &format!("No results found for query `{query}` in crate `{crate_name}`"),
+            );
+        };
+        let records = results
+            .iter()
+            .map(|hit| hit_json(hit, path))
+            .collect::<Result<_, _>>()?;
+        return emit(output, records);
+    }
+
+    let color = output.color;
+    let terminal = output.terminal();
+    let out = &mut output.out;
+
+    if let Some(results) = doc.search_detailed(query, Some(n)) {
         if let Some((first, rest)) = results.split_first() {
-            println!("{}", first.path.join("::"));
-            if let Some(docs) = &first.docs {
-                println!("\n{}", docs);
+            writeln!(out, "{}", display_path(first, path, color))?;
+            if let Some(docs) = first.item.render_docs(&terminal) {
+                write!(out, "\n{docs}")?;
             }
 
             if !rest.is_empty() {
-                println!("\n---\n");
-                for item in rest {
-                    println!("{}", item.path.join("::"));
+                writeln!(out, "\n---\n")?;
+                for hit in rest {
+                    writeln!(out, "{}", display_path(hit, path, color))?;
                 }
             }
         }
     } else {
-        println!(
-            "No results found for query `{}` in crate `{}`",
-            args.query, args.crate_name
-        );
+        writeln!(
+            out,
+            "No results found for query `{query}` in crate `{crate_name}`"
+        )?;
     }
 
     Ok(())
 }
+
+/// Whether to print ANSI colors, respecting `NO_COLOR` (https://no-color.org)
+fn use_color() -> bool {
+    std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()) && std::io::stdout().is_terminal()
+}
+
+/// Returns the path to print for a hit, highlighting the matched characters if it is the matched key
+fn display_path(hit: &SearchHit, preference: Option<PathArg>, color: bool) -> String {
+    let Some(preference) = preference else {
+        return highlight(hit.matched_key, &hit.match_indices, color);
+    };
+
+    let path = hit.item.path_by(preference.into()).join("::");
+    if path == hit.matched_key {
+        highlight(hit.matched_key, &hit.match_indices, color)
+    } else {
+        path
+    }
+}
+
+/// Makes the characters of `key` at the given char indices bold and colored
+fn highlight(key: &str, indices: &[usize], color: bool) -> String {
+    if !color || indices.is_empty() {
+        return key.to_string();
+    }
+
+    let mut out = String::with_capacity(key.len() + indices.len() * 10);
+    for (i, c) in key.chars().enumerate() {
+        if indices.binary_search(&i).is_ok() {
+            out.push_str("\x1b[1;33m");
+            out.push(c);
+            out.push_str("\x1b[0m");
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
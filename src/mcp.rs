@@ -0,0 +1,377 @@
+//! The Model Context Protocol server of `docsrs mcp`
+//!
+//! Speaks JSON-RPC over stdio, one message per line. Crates are fetched on their
+//! first use, or read from the rustdoc JSON files passed with `--docs`, and kept
+//! for the lifetime of the server.
+
+use crate::cache::Cache;
+use crate::{find, hit_json, item_json};
+use docsrs::{Error, MethodOrigin};
+use rustdoc_types::ItemKind;
+use serde_json::{Map, Value, json};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+/// The protocol version used if the client asks for one we don't know
+const PROTOCOL_VERSION: &str = "2025-06-18";
+const SUPPORTED_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", "2025-06-18"];
+
+const TOOLS: &[&str] = &["search_docs", "get_item", "list_module", "list_methods"];
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serves requests from stdin until it is closed, with the crates of `docs` read from files
+pub fn run(docs: Vec<PathBuf>) -> Result<(), Error> {
+    let mut server = Server {
+        cache: Cache::with_files(usize::MAX, docs),
+    };
+    let mut stdout = std::io::stdout().lock();
+
+    for line in std::io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => server.handle(message),
+            Err(e) => Some(error(Value::Null, PARSE_ERROR, e.to_string())),
+        };
+        if let Some(response) = response {
+            writeln!(stdout, "{}", serde_json::to_string(&response)?)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+struct Server {
+    /// The crates loaded by earlier calls
+    cache: Cache,
+}
+
+/// Why a tool call failed
+enum ToolError {
+    /// The arguments don't match the input schema, a protocol error
+    InvalidArguments(String),
+    /// The tool ran but failed, reported to the model as the tool result
+    Failed(String),
+}
+
+impl From<Error> for ToolError {
+    fn from(e: Error) -> Self {
+        ToolError::Failed(e.to_string())
+    }
+}
+
+impl Server {
+    /// Answers a message, `None` for notifications
+    fn handle(&mut self, message: Value) -> Option<Value> {
+        let id = message.get("id")?.clone();
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+
+        let result = match method {
+            "initialize" => {
+                let requested = params["protocolVersion"].as_str().unwrap_or_default();
+                let version = SUPPORTED_VERSIONS
+                    .iter()
+                    .find(|version| **version == requested)
+                    .unwrap_or(&PROTOCOL_VERSION);
+                json!({
+                    "protocolVersion": version,
+                    "capabilities": { "tools": { "listChanged": false } },
+                    "serverInfo": { "name": "docsrs", "version": env!("CARGO_PKG_VERSION") },
+                })
+            }
+            "ping" => json!({}),
+            "tools/list" => json!({ "tools": tools() }),
+            "tools/call" => {
+                let name = params["name"].as_str().unwrap_or_default();
+                let arguments = params["arguments"].as_object().cloned().unwrap_or_default();
+                match self.call(name, &arguments) {
+                    Ok(structured) => json!({
+                        "content": [{ "type": "text", "text": structured.to_string() }],
+                        "structuredContent": structured,
+                        "isError": false,
+                    }),
+                    Err(ToolError::Failed(message)) => json!({
+                        "content": [{ "type": "text", "text": message }],
+                        "isError": true,
+                    }),
+                    Err(ToolError::InvalidArguments(message)) => {
+                        return Some(error(id, INVALID_PARAMS, message));
+                    }
+                }
+            }
+            method => {
+                return Some(error(
+                    id,
+                    METHOD_NOT_FOUND,
+                    format!("unknown method {method}"),
+                ));
+            }
+        };
+        Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+    }
+
+    fn call(&mut self, tool: &str, arguments: &Map<String, Value>) -> Result<Value, ToolError> {
+        if !TOOLS.contains(&tool) {
+            return Err(ToolError::InvalidArguments(format!("unknown tool {tool}")));
+        }
+
+        let argument = |name: &str| arguments.get(name).and_then(Value::as_str);
+        let required = |name: &str| {
+            argument(name).ok_or_else(|| {
+                ToolError::InvalidArguments(format!("missing string argument `{name}`"))
+            })
+        };
+        let crate_name = required("crate")?;
+        let version = argument("version").unwrap_or("latest");
+
+        match tool {
+            "search_docs" => {
+                let query = required("query")?;
+                let kind = argument("kind")
+                    .map(|kind| {
+                        serde_json::from_value::<ItemKind>(kind.into()).map_err(|_| {
+                            ToolError::InvalidArguments(format!("unknown item kind `{kind}`"))
+                        })
+                    })
+                    .transpose()?;
+                let limit = arguments.get("limit").and_then(Value::as_u64).unwrap_or(10);
+
+                let loaded = self.cache.get(crate_name, version)?;
+                let doc = loaded.indexed();
+                let results = doc
+                    .search_detailed(query, None)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter(|hit| kind.is_none() || hit.item.kind == kind)
+                    .take(limit as usize)
+                    .map(|hit| hit_json(hit, None))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(json!({ "results": results }))
+            }
+            "get_item" => {
+                let path = required("path")?;
+                let loaded = self.cache.get(crate_name, version)?;
+                let doc = loaded.indexed();
+                let item = doc
+                    .get_by_path(path)
+                    .ok_or_else(|| not_found(crate_name, path))?;
+                Ok(json!({ "item": item_json(item)? }))
+            }
+            "list_module" => {
+                let path = argument("path");
+                let loaded = self.cache.get(crate_name, version)?;
+                let tree = loaded.parsed.module_tree();
+                let module = match path {
+                    Some(path) => tree.find(path),
+                    None => Some(tree.root()),
+                }
+                .filter(|id| tree.kind(id) == Some(ItemKind::Module))
+                .ok_or_else(|| not_found(crate_name, path.unwrap_or(crate_name)))?;
+
+                let items: Vec<Value> = tree
+                    .children(module)
+                    .into_iter()
+                    .map(|id| {
+                        let summary = tree
+                            .item(id)
+                            .and_then(|item| item.docs.as_deref())
+                            .and_then(|docs| docs.lines().next());
+                        json!({ "name": tree.name(id), "kind": tree.kind(id), "summary": summary })
+                    })
+                    .collect();
+                Ok(json!({ "items": items }))
+            }
+            "list_methods" => {
+                let path = required("type")?;
+                let loaded = self.cache.get(crate_name, version)?;
+                let doc = loaded.indexed();
+                let kinds = [
+                    ItemKind::Struct,
+                    ItemKind::Enum,
+                    ItemKind::Union,
+                    ItemKind::Primitive,
+                ];
+                let type_item = doc
+                    .lookup(path)
+                    .into_iter()
+                    .find(|item| item.kind.is_some_and(|kind| kinds.contains(&kind)))
+                    .or_else(|| find(doc, path, &kinds))
+                    .ok_or_else(|| not_found(crate_name, path))?;
+
+                let methods: Vec<Value> = doc
+                    .methods_of(type_item)
+                    .iter()
+                    .map(|method| {
+                        let (origin, via) = origin_json(&method.origin);
+                        json!({
+                            "name": method.item.name,
+                            "origin": origin,
+                            "via": via,
+                            "signature": method.item.signature(),
+                            "deprecated": method.item.deprecation.is_some(),
+                        })
+                    })
+                    .collect();
+                Ok(json!({ "type": type_item.path.join("::"), "methods": methods }))
+            }
+            _ => unreachable!("checked above"),
+        }
+    }
+}
+
+fn not_found(crate_name: &str, path: &str) -> ToolError {
+    ToolError::Failed(format!("No item `{path}` found in crate `{crate_name}`"))
+}
+
+/// How a method is reachable, with the trait or deref target it is reachable through
+fn origin_json(origin: &MethodOrigin) -> (&'static str, Option<String>) {
+    match origin {
+        MethodOrigin::Inherent => ("inherent", None),
+        MethodOrigin::Trait { trait_path } => ("trait", Some(trait_path.join("::"))),
+        MethodOrigin::Blanket { trait_path } => ("blanket", Some(trait_path.join("::"))),
+        MethodOrigin::Deref { target } => ("deref", Some(target.join("::"))),
+    }
+}
+
+fn error(id: Value, code: i64, message: String) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// The tool definitions with the JSON schemas of their arguments and results
+fn tools() -> Value {
+    let crate_args = json!({
+        "crate": { "type": "string", "description": "The crate name, e.g. `tokio`" },
+        "version": { "type": "string", "description": "The crate version, defaults to `latest`" },
+    });
+    let with = |properties: Value| {
+        let mut all = crate_args.clone();
+        all.as_object_mut()
+            .unwrap()
+            .extend(properties.as_object().cloned().unwrap_or_default());
+        all
+    };
+    let nullable_string = json!({ "type": ["string", "null"] });
+    let item = json!({
+        "type": "object",
+        "properties": {
+            "path": { "type": "string" },
+            "name": { "type": "string" },
+            "kind": nullable_string,
+            "canonical_path": nullable_string,
+            "version": nullable_string,
+            "url": nullable_string,
+            "signature": nullable_string,
+            "deprecation": { "type": ["object", "null"] },
+            "docs": nullable_string,
+        },
+        "required": ["path", "name"],
+    });
+    let mut hit = item.clone();
+    hit["properties"]["score"] = json!({ "type": "integer" });
+
+    json!([
+        {
+            "name": "search_docs",
+            "description": "Fuzzy search the docs of a crate by path, e.g. `Mutex::lock` or `spawn`. Returns the best matching items with their signatures and docs.",
+            "inputSchema": {
+                "type": "object",
+                "properties": with(json!({
+                    "query": { "type": "string", "description": "The search query" },
+                    "kind": {
+                        "type": "string",
+                        "description": "Only return items of this kind, e.g. `struct`, `function`, `trait`",
+                    },
+                    "limit": { "type": "integer", "description": "The maximum number of results, defaults to 10" },
+                })),
+                "required": ["crate", "query"],
+            },
+            "outputSchema": {
+                "type": "object",
+                "properties": { "results": { "type": "array", "items": hit } },
+                "required": ["results"],
+            },
+        },
+        {
+            "name": "get_item",
+            "description": "Get the signature and docs of the item at an exact path, e.g. `tokio::sync::Mutex`.",
+            "inputSchema": {
+                "type": "object",
+                "properties": with(json!({
+                    "path": { "type": "string", "description": "The full path of the item" },
+                })),
+                "required": ["crate", "path"],
+            },
+            "outputSchema": {
+                "type": "object",
+                "properties": { "item": item },
+                "required": ["item"],
+            },
+        },
+        {
+            "name": "list_module",
+            "description": "List the items of a module, e.g. `tokio::sync`, with their kinds and the first line of their docs.",
+            "inputSchema": {
+                "type": "object",
+                "properties": with(json!({
+                    "path": { "type": "string", "description": "The path of the module, defaults to the crate root" },
+                })),
+                "required": ["crate"],
+            },
+            "outputSchema": {
+                "type": "object",
+                "properties": {
+                    "items": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": nullable_string,
+                                "kind": nullable_string,
+                                "summary": nullable_string,
+                            },
+                        },
+                    },
+                },
+                "required": ["items"],
+            },
+        },
+        {
+            "name": "list_methods",
+            "description": "List every method callable on a type, including trait methods and methods reachable through `Deref`.",
+            "inputSchema": {
+                "type": "object",
+                "properties": with(json!({
+                    "type": { "type": "string", "description": "The path of the type, e.g. `tokio::sync::Mutex`" },
+                })),
+                "required": ["crate", "type"],
+            },
+            "outputSchema": {
+                "type": "object",
+                "properties": {
+                    "type": { "type": "string" },
+                    "methods": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": { "type": "string" },
+                                "origin": { "enum": ["inherent", "trait", "blanket", "deref"] },
+                                "via": nullable_string,
+                                "signature": nullable_string,
+                                "deprecated": { "type": "boolean" },
+                            },
+                        },
+                    },
+                },
+                "required": ["type", "methods"],
+            },
+        },
+    ])
+}
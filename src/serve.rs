@@ -0,0 +1,284 @@
+//! The HTTP server of `docsrs serve`
+
+use crate::cache::{Cache, Loaded};
+use crate::{hit_json, item_json};
+use docsrs::Error;
+use serde_json::{Value, json};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::rc::Rc;
+use tiny_http::{Header, Method, Response, Server};
+use url::Url;
+
+/// The search page served at `/`
+const INDEX_HTML: &str = include_str!("serve.html");
+
+type Page = Response<Cursor<Vec<u8>>>;
+type Query<'a> = HashMap<Cow<'a, str>, Cow<'a, str>>;
+/// The JSON body of a response, or its error status and message
+type Handled = Result<Value, (u16, String)>;
+
+/// A crate kept in memory
+struct Served {
+    name: String,
+    /// The version the docs are for, `latest` resolved if the docs tell
+    version: String,
+    loaded: Rc<Loaded>,
+}
+
+/// Loads the crates, given as `name` or `name@version`, and serves them on `bind`
+/// until killed
+///
+/// The crates of `docs` are read from these rustdoc JSON files instead of fetched.
+pub fn run(bind: IpAddr, port: u16, crates: &[String], docs: Vec<PathBuf>) -> Result<(), Error> {
+    let served = load(&mut Cache::with_files(usize::MAX, docs), crates)?;
+
+    let address = SocketAddr::new(bind, port);
+    let server = Server::http(address).map_err(std::io::Error::other)?;
+    eprintln!("serving on http://{address}");
+    for request in server.incoming_requests() {
+        let response = handle(&served, request.method(), request.url());
+        if let Err(e) = request.respond(response) {
+            eprintln!("failed to respond: {e}");
+        }
+    }
+    Ok(())
+}
+
+/// Loads the crates, given as `name` or `name@version`, by their [`crate_key`]
+fn load(cache: &mut Cache, crates: &[String]) -> Result<HashMap<String, Served>, Error> {
+    let mut served = HashMap::new();
+    for spec in crates {
+        let (name, version) = spec.split_once('@').unwrap_or((spec, "latest"));
+        eprintln!("loading {name} {version}");
+        let loaded = cache.get(name, version)?;
+        let version = loaded
+            .indexed()
+            .get_by_path(&crate_key(name))
+            .and_then(|root| root.crate_version.clone())
+            .unwrap_or_else(|| version.to_string());
+        let name = name.to_string();
+        served.insert(
+            crate_key(&name),
+            Served {
+                name,
+                version,
+                loaded,
+            },
+        );
+    }
+    Ok(served)
+}
+
+/// The name crates are looked up by, `tokio-util` and `tokio_util` are the same crate
+fn crate_key(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+/// Answers a request for `url`, the path and query of the request
+fn handle(served: &HashMap<String, Served>, method: &Method, url: &str) -> Page {
+    if method != &Method::Get {
+        return error(405, "only GET is supported");
+    }
+    let Ok(url) = Url::parse(&format!("http://localhost{url}")) else {
+        return error(400, "invalid url");
+    };
+    let query: HashMap<_, _> = url.query_pairs().collect();
+    let segments: Vec<String> = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|segment| !segment.is_empty())
+        .map(percent_decode)
+        .collect();
+
+    let result = match segments.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] => {
+            let header = Header::from_bytes("Content-Type", "text/html; charset=utf-8").unwrap();
+            return Response::from_string(INDEX_HTML).with_header(header);
+        }
+        ["crates"] => {
+            let mut crates: Vec<&Served> = served.values().collect();
+            crates.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(crates
+                .into_iter()
+                .map(|served| json!({ "name": served.name, "version": served.version }))
+                .collect())
+        }
+        ["search"] => search(served, &query),
+        ["item", crate_name, path] => item(served, crate_name, path),
+        _ => Err((404, "not found".to_string())),
+    };
+
+    match result {
+        Ok(body) => json_response(200, &body),
+        Err((status, message)) => error(status, &message),
+    }
+}
+
+/// `GET /search?crate=tokio&q=spawn&n=10`
+fn search(served: &HashMap<String, Served>, query: &Query) -> Handled {
+    let crate_name = query
+        .get("crate")
+        .ok_or((400, "missing query parameter `crate`".to_string()))?;
+    let q = query
+        .get("q")
+        .ok_or((400, "missing query parameter `q`".to_string()))?;
+    let n = match query.get("n") {
+        Some(n) => n
+            .parse()
+            .map_err(|_| (400, format!("invalid number of results `{n}`")))?,
+        None => 10,
+    };
+
+    let doc = crate_doc(served, crate_name)?.loaded.indexed();
+    doc.search_detailed(q, Some(n))
+        .unwrap_or_default()
+        .iter()
+        .map(|hit| hit_json(hit, None))
+        .collect::<Result<_, _>>()
+        .map_err(|e| (500, e.to_string()))
+}
+
+/// `GET /item/tokio/tokio::sync::Mutex`
+fn item(served: &HashMap<String, Served>, crate_name: &str, path: &str) -> Handled {
+    let doc = crate_doc(served, crate_name)?.loaded.indexed();
+    let item = doc
+        .get_by_path(path)
+        .ok_or((404, format!("no item `{path}` in `{crate_name}`")))?;
+    item_json(item).map_err(|e| (500, e.to_string()))
+}
+
+fn crate_doc<'a>(
+    served: &'a HashMap<String, Served>,
+    crate_name: &str,
+) -> Result<&'a Served, (u16, String)> {
+    served
+        .get(&crate_key(crate_name))
+        .ok_or((404, format!("crate `{crate_name}` isn't served")))
+}
+
+/// Decodes the `%XX` escapes of a path segment, invalid escapes are kept as they are
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok())
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn json_response(status: u16, body: &Value) -> Page {
+    let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+    Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header)
+}
+
+fn error(status: u16, message: &str) -> Page {
+    json_response(status, &json!({ "error": message }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn fixture() -> HashMap<String, Served> {
+        let files = vec![PathBuf::from("tests/fixtures/fixture.json")];
+        load(
+            &mut Cache::with_files(usize::MAX, files),
+            &["fixture".to_string()],
+        )
+        .unwrap()
+    }
+
+    /// The status and JSON body of the response to a request
+    fn get(served: &HashMap<String, Served>, method: Method, url: &str) -> (u16, Value) {
+        let response = handle(served, &method, url);
+        let status = response.status_code().0;
+        let mut body = String::new();
+        response.into_reader().read_to_string(&mut body).unwrap();
+        (
+            status,
+            serde_json::from_str(&body).unwrap_or(Value::String(body)),
+        )
+    }
+
+    #[test]
+    fn routes() {
+        let served = fixture();
+
+        let (status, crates) = get(&served, Method::Get, "/crates");
+        assert_eq!(status, 200);
+        assert_eq!(crates, json!([{ "name": "fixture", "version": "0.1.0" }]));
+
+        let (status, hits) = get(
+            &served,
+            Method::Get,
+            "/search?crate=fixture&q=Config%3A%3Anew&n=1",
+        );
+        assert_eq!(status, 200);
+        assert_eq!(hits.as_array().unwrap().len(), 1);
+        assert_eq!(hits[0]["path"], "fixture::config::Config::new");
+
+        let (status, item) = get(
+            &served,
+            Method::Get,
+            "/item/fixture/fixture%3A%3Aclient%3A%3AClient",
+        );
+        assert_eq!(status, 200);
+        assert_eq!(item["path"], "fixture::client::Client");
+        assert_eq!(item["kind"], "struct");
+
+        let (status, page) = get(&served, Method::Get, "/");
+        assert_eq!(status, 200);
+        assert!(page.as_str().unwrap().starts_with("<!"));
+    }
+
+    #[test]
+    fn errors() {
+        let served = fixture();
+        let cases = [
+            (Method::Post, "/crates", 405),
+            (Method::Get, "/nothing", 404),
+            (Method::Get, "/item/fixture", 404),
+            (Method::Get, "/item/fixture/fixture::Missing", 404),
+            (Method::Get, "/item/other/other::Thing", 404),
+            (Method::Get, "/search?q=Client", 400),
+            (Method::Get, "/search?crate=fixture", 400),
+            (Method::Get, "/search?crate=fixture&q=Client&n=ten", 400),
+            (Method::Get, "/search?crate=other&q=Client", 404),
+        ];
+        for (method, url, expected) in cases {
+            let (status, body) = get(&served, method, url);
+            assert_eq!(status, expected, "{url}");
+            assert!(body["error"].is_string(), "{url}");
+        }
+    }
+
+    #[test]
+    fn decode_segments() {
+        assert_eq!(percent_decode("tokio%3A%3Async"), "tokio::sync");
+        assert_eq!(percent_decode("%E2%9C%93"), "✓");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+    }
+}
@@ -0,0 +1,383 @@
+//! The interactive terminal UI of `docsrs tui`
+
+use docsrs::{Doc, Error, Indexed, Item, MarkdownLine, MarkdownSpan, SearchHit};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use rustdoc_types::ItemKind;
+use std::process::{Command, Stdio};
+
+/// The maximum number of results to show
+const MAX_RESULTS: usize = 100;
+
+const HELP: &str = "type to search  ↑/↓ select  PgUp/PgDn scroll  Tab link  Enter follow  Esc back  Ctrl+O open in browser";
+
+/// Runs the UI until the user quits, the terminal is restored even on errors
+pub fn run(crate_name: &str, doc: &Doc<Indexed>) -> Result<(), Error> {
+    let mut terminal = ratatui::init();
+    let result = App::new(crate_name, doc).run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+struct App<'a> {
+    crate_name: &'a str,
+    doc: &'a Doc<Indexed>,
+    query: String,
+    hits: Vec<SearchHit<'a>>,
+    list: ListState,
+    /// The items opened by following links, the last one is shown in the preview
+    history: Vec<&'a Item>,
+    /// The index of the selected link of the previewed item
+    link: usize,
+    scroll: u16,
+    status: Option<String>,
+}
+
+impl<'a> App<'a> {
+    fn new(crate_name: &'a str, doc: &'a Doc<Indexed>) -> Self {
+        Self {
+            crate_name,
+            doc,
+            query: String::new(),
+            hits: Vec::new(),
+            list: ListState::default(),
+            history: Vec::new(),
+            link: 0,
+            scroll: 0,
+            status: None,
+        }
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> Result<(), Error> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && !self.handle(key)
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    /// The item shown in the preview pane
+    fn previewed(&self) -> Option<&'a Item> {
+        self.history.last().copied().or_else(|| {
+            let selected = self.list.selected()?;
+            Some(self.hits.get(selected)?.item)
+        })
+    }
+
+    /// Handles a key press, returns `false` to quit
+    fn handle(&mut self, key: KeyEvent) -> bool {
+        self.status = None;
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('c') if ctrl => return false,
+            KeyCode::Char('o') if ctrl => self.open_in_browser(),
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.search();
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.search();
+            }
+            KeyCode::Up => self.select(self.list.selected().map_or(0, |i| i.saturating_sub(1))),
+            KeyCode::Down => self.select(self.list.selected().map_or(0, |i| i + 1)),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::Tab => self.cycle_link(1),
+            KeyCode::BackTab => self.cycle_link(-1),
+            KeyCode::Enter => self.follow_link(),
+            KeyCode::Esc => {
+                if self.history.pop().is_none() {
+                    return false;
+                }
+                self.link = 0;
+                self.scroll = 0;
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn search(&mut self) {
+        self.hits = if self.query.trim().is_empty() {
+            Vec::new()
+        } else {
+            self.doc
+                .search_detailed(&self.query, MAX_RESULTS)
+                .unwrap_or_default()
+        };
+        self.list.select((!self.hits.is_empty()).then_some(0));
+        self.history.clear();
+        self.link = 0;
+        self.scroll = 0;
+    }
+
+    fn select(&mut self, index: usize) {
+        if self.hits.is_empty() {
+            return;
+        }
+        self.list.select(Some(index.min(self.hits.len() - 1)));
+        self.history.clear();
+        self.link = 0;
+        self.scroll = 0;
+    }
+
+    fn cycle_link(&mut self, step: isize) {
+        let Some(item) = self.previewed() else {
+            return;
+        };
+        let count = self.doc.links_of(item).len();
+        if count > 0 {
+            self.link = (self.link as isize + step).rem_euclid(count as isize) as usize;
+        }
+    }
+
+    fn follow_link(&mut self) {
+        let Some(item) = self.previewed() else {
+            return;
+        };
+        match self.doc.links_of(item).get(self.link) {
+            Some((_, target)) => {
+                self.history.push(target);
+                self.link = 0;
+                self.scroll = 0;
+            }
+            None => self.status = Some("no links to follow".to_string()),
+        }
+    }
+
+    fn open_in_browser(&mut self) {
+        let Some(item) = self.previewed() else {
+            return;
+        };
+        self.status = Some(match item.url() {
+            Ok(Some(url)) => match open(url.as_str()) {
+                Ok(()) => format!("opened {url}"),
+                Err(e) => format!("failed to open {url}: {e}"),
+            },
+            Ok(None) => "the item has no page on docs.rs".to_string(),
+            Err(e) => e.to_string(),
+        });
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [input, main, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [results, preview] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(main);
+
+        let title = format!(" {} ", self.crate_name);
+        let query = Paragraph::new(self.query.as_str()).block(Block::bordered().title(title));
+        frame.render_widget(query, input);
+        frame.set_cursor_position((input.x + 1 + self.query.chars().count() as u16, input.y + 1));
+
+        let items: Vec<ListItem> = self.hits.iter().map(result_line).collect();
+        let list = List::new(items)
+            .block(Block::bordered().title(format!(" {} results ", self.hits.len())))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, results, &mut self.list);
+
+        let text = self
+            .previewed()
+            .map(|item| self.preview(item))
+            .unwrap_or_default();
+        let preview_block = match self.history.len() {
+            0 => Block::bordered(),
+            depth => Block::bordered().title(format!(" {depth} link(s) deep, Esc to go back ")),
+        };
+        let paragraph = Paragraph::new(text)
+            .block(preview_block)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(paragraph, preview);
+
+        let status = self.status.as_deref().unwrap_or(HELP);
+        frame.render_widget(Line::from(status).dark_gray(), footer);
+    }
+
+    /// The path, signature, docs and links of an item
+    fn preview(&self, item: &'a Item) -> Text<'static> {
+        let path = item
+            .canonical_path
+            .as_ref()
+            .unwrap_or(&item.path)
+            .join("::");
+        let (icon, color) = icon(item.kind);
+        let mut lines = vec![Line::from(vec![
+            Span::styled(format!("{icon} "), Style::new().fg(color).bold()),
+            Span::raw(path).bold(),
+        ])];
+
+        if let Some(signature) = item.signature() {
+            lines.push(Line::default());
+            lines.extend(
+                signature
+                    .lines()
+                    .map(|line| Line::styled(line.to_string(), Color::Yellow)),
+            );
+        }
+        if let Some(deprecation) = &item.deprecation {
+            let note = deprecation.note.as_deref().unwrap_or("deprecated");
+            lines.push(Line::default());
+            lines.push(Line::styled(format!("Deprecated: {note}"), Color::Red));
+        }
+        if let Some(docs) = &item.docs {
+            lines.push(Line::default());
+            lines.extend(markdown(docs));
+        }
+
+        let links = self.doc.links_of(item);
+        if !links.is_empty() {
+            lines.push(Line::default());
+            lines.push(Line::from("Links").bold());
+            for (i, (text, target)) in links.into_iter().enumerate() {
+                let line = Line::from(vec![
+                    Span::raw(if i == self.link { "> " } else { "  " }),
+                    Span::raw(text.trim_matches('`').to_string()).underlined(),
+                    Span::raw(format!(" {}", target.path.join("::"))).dark_gray(),
+                ]);
+                lines.push(if i == self.link {
+                    line.reversed()
+                } else {
+                    line
+                });
+            }
+        }
+        Text::from(lines)
+    }
+}
+
+fn result_line<'a>(hit: &SearchHit<'a>) -> ListItem<'a> {
+    let (icon, color) = icon(hit.item.kind);
+    ListItem::new(Line::from(vec![
+        Span::styled(format!("{icon} "), Style::new().fg(color).bold()),
+        Span::raw(hit.matched_key),
+    ]))
+}
+
+/// The letter and color representing an item kind in the UI
+fn icon(kind: Option<ItemKind>) -> (char, Color) {
+    match kind {
+        Some(ItemKind::Module) => ('m', Color::Blue),
+        Some(ItemKind::Struct) => ('S', Color::Green),
+        Some(ItemKind::Enum) => ('E', Color::Green),
+        Some(ItemKind::Union) => ('U', Color::Green),
+        Some(ItemKind::Variant) => ('v', Color::Cyan),
+        Some(ItemKind::StructField) => ('f', Color::Cyan),
+        Some(ItemKind::Trait | ItemKind::TraitAlias) => ('T', Color::Magenta),
+        Some(ItemKind::Function) => ('ƒ', Color::Yellow),
+        Some(ItemKind::TypeAlias | ItemKind::AssocType) => ('t', Color::Green),
+        Some(ItemKind::Constant | ItemKind::AssocConst | ItemKind::Static) => ('c', Color::Red),
+        Some(ItemKind::Macro | ItemKind::ProcAttribute | ItemKind::ProcDerive) => {
+            ('!', Color::LightRed)
+        }
+        _ => ('·', Color::Gray),
+    }
+}
+
+/// Renders markdown line by line: headings, code blocks, inline code and links are styled
+///
+/// The lines are the ones [`TerminalOptions::render`](docsrs::TerminalOptions::render)
+/// shows, without hidden lines of Rust code and the definitions of reference links.
+fn markdown(docs: &str) -> Vec<Line<'static>> {
+    MarkdownLine::parse(docs)
+        .into_iter()
+        .map(|line| match line {
+            MarkdownLine::Heading { text, .. } => Line::from(inline(text)).bold(),
+            MarkdownLine::Code { line, .. } => Line::styled(format!("    {line}"), Color::Cyan),
+            MarkdownLine::Text(line) => Line::from(inline(line)),
+        })
+        .collect()
+}
+
+/// Styles `inline code`, **bold** text and [links], dropping the link targets
+fn inline(line: &str) -> Vec<Span<'static>> {
+    MarkdownSpan::parse(line)
+        .into_iter()
+        .map(|span| match span {
+            MarkdownSpan::Text(text) => Span::raw(text.to_string()),
+            MarkdownSpan::Code(code) => Span::styled(code.to_string(), Color::Yellow),
+            MarkdownSpan::Bold(text) => Span::raw(text.replace('`', "")).bold(),
+            MarkdownSpan::Link(text) => Span::raw(text.replace('`', "")).underlined(),
+        })
+        .collect()
+}
+
+/// Opens a url with the platform's default browser
+fn open(url: &str) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(drop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn render_markdown() {
+        let docs = "# Usage\n\
+                    Call [`spawn`].\n\
+                    \n\
+                    ```\n# use tokio::spawn;\n##[tokio::main]\n```\n\
+                    ```sh\n# a comment\ncargo run\n```\n\
+                    [`spawn`]: crate::spawn";
+        let lines = markdown(docs);
+        let texts: Vec<String> = lines.iter().map(text).collect();
+        assert_eq!(
+            texts,
+            [
+                "Usage",
+                "Call spawn.",
+                "",
+                "    #[tokio::main]",
+                "    # a comment",
+                "    cargo run"
+            ]
+        );
+        assert!(lines[0].style.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(lines[3].style.fg, Some(Color::Cyan));
+    }
+
+    #[test]
+    fn style_inline() {
+        let spans = inline("Use `Vec` or [`Box`](std::boxed::Box), see [docs][1].");
+        let texts: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(texts, ["Use ", "Vec", " or ", "Box", ", see ", "docs", "."]);
+        assert_eq!(spans[1].style.fg, Some(Color::Yellow));
+        assert!(spans[3].style.add_modifier.contains(Modifier::UNDERLINED));
+        assert!(spans[5].style.add_modifier.contains(Modifier::UNDERLINED));
+    }
+}
@@ -0,0 +1,287 @@
+//! A small crate used as a test fixture for `docsrs`.
+//!
+//! `fixture.json` is generated from this file, regenerate it from the repository root with:
+//!
+//! ```sh
+//! RUSTC_BOOTSTRAP=1 rustdoc -Z unstable-options --output-format json --document-hidden-items \
+//!     --edition 2024 --crate-name fixture --crate-version 0.1.0 -o tests/fixtures tests/fixtures/fixture.rs
+//! ```
+
+pub mod config {
+    /// Configuration for a [`Client`](crate::client::Client).
+    pub struct Config {
+        /// Request timeout in seconds
+        pub timeout: u32,
+        /// How often a request is retried
+        pub retries: u8,
+        secret: String,
+    }
+
+    impl Config {
+        /// The timeout used by [`Config::new`]
+        pub const DEFAULT_TIMEOUT: u32 = 30;
+
+        /// Creates a config with the default timeout.
+        pub fn new() -> Self {
+            Self {
+                timeout: Self::DEFAULT_TIMEOUT,
+                retries: 3,
+                secret: String::new(),
+            }
+        }
+
+        /// Returns the timeout in seconds.
+        pub fn timeout(&self) -> u32 {
+            self.timeout
+        }
+
+        /// Returns the secret.
+        pub fn secret(&self) -> &str {
+            &self.secret
+        }
+    }
+
+    impl Default for Config {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// A duration in milliseconds.
+    pub struct Millis(pub u64);
+
+    impl std::fmt::Display for Millis {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}ms", self.0)
+        }
+    }
+}
+
+pub mod client {
+    use crate::config::Config;
+
+    /// A client that sends requests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use fixture::client::Client;
+    /// let client = Client::new(Default::default());
+    /// ```
+    pub struct Client {
+        config: Config,
+    }
+
+    impl Client {
+        /// Creates a new client.
+        pub fn new(config: Config) -> Self {
+            Self { config }
+        }
+
+        /// Returns the [`Config`] of this client, as passed to [new].
+        ///
+        /// [`Config`]: crate::config::Config
+        /// [new]: Self::new
+        pub fn config(&self) -> &Config {
+            &self.config
+        }
+
+        /// Sends a request.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the request times out.
+        pub fn send(&self, body: &[u8]) -> Result<usize, String> {
+            Ok(body.len())
+        }
+    }
+}
+
+pub mod shapes {
+    /// A geometric shape.
+    pub enum Shape {
+        /// A circle
+        Circle {
+            /// The radius of the circle
+            radius: f64,
+        },
+        /// A rectangle with width and height
+        Rect(f64, f64),
+        /// Nothing at all
+        Empty,
+    }
+
+    /// Something with an area.
+    pub trait Area {
+        /// The type the area is measured in
+        type Output;
+
+        /// The number of sides
+        const SIDES: u32;
+
+        /// Computes the area.
+        fn area(&self) -> Self::Output;
+
+        /// Describes the shape.
+        fn describe(&self) -> String {
+            String::from("a shape")
+        }
+    }
+
+    impl Area for Shape {
+        type Output = f64;
+
+        const SIDES: u32 = 0;
+
+        fn area(&self) -> f64 {
+            match self {
+                Shape::Circle { radius } => radius * radius * 3.14,
+                Shape::Rect(w, h) => w * h,
+                Shape::Empty => 0.0,
+            }
+        }
+    }
+
+    impl Shape {
+        /// Returns whether the shape is empty.
+        pub fn is_empty(&self) -> bool {
+            matches!(self, Shape::Empty)
+        }
+    }
+}
+
+mod inner {
+    /// A struct only reachable through a glob re-export.
+    pub struct Reexported {
+        /// Some value
+        pub value: i32,
+    }
+
+    impl Reexported {
+        /// Does nothing.
+        pub fn noop(&self) {}
+    }
+
+    /// A function only reachable through a glob re-export.
+    pub fn reexported_fn() {}
+}
+
+pub use inner::*;
+
+/// Commonly used items.
+pub mod prelude {
+    pub use crate::config::Config;
+    pub use crate::shapes::*;
+}
+
+/// Re-exports everything of `cycle_b`, which re-exports everything of this module.
+pub mod cycle_a {
+    pub use crate::cycle_b::*;
+
+    /// Defined in `cycle_a`.
+    pub struct A;
+}
+
+/// Re-exports everything of `cycle_a`.
+pub mod cycle_b {
+    pub use crate::cycle_a::*;
+
+    /// Defined in `cycle_b`.
+    pub struct B;
+}
+
+/// A growable byte buffer.
+///
+/// The bytes are indexed like a slice, bytes[i] is the byte at `i` and
+/// `bytes[..n]` the first `n` bytes.
+pub struct Buffer {
+    bytes: Vec<u8>,
+}
+
+impl Buffer {
+    /// Returns the number of bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns `true` if the buffer holds no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+/// Wraps a [`Buffer`] and derefs to it.
+pub struct Wrapper {
+    buffer: Buffer,
+}
+
+impl Wrapper {
+    /// Consumes the wrapper.
+    pub fn into_inner(self) -> Buffer {
+        self.buffer
+    }
+}
+
+impl std::ops::Deref for Wrapper {
+    type Target = Buffer;
+
+    fn deref(&self) -> &Buffer {
+        &self.buffer
+    }
+}
+
+/// Greets someone.
+pub trait Greet {
+    /// Returns a greeting.
+    fn greet(&self) -> String;
+}
+
+impl<T: std::fmt::Display> Greet for T {
+    fn greet(&self) -> String {
+        format!("hello {self}")
+    }
+}
+
+/// Bits of a 32-bit value.
+pub union Bits {
+    /// As an integer
+    pub int: u32,
+    /// As a float
+    pub float: f32,
+}
+
+/// The maximum number of retries.
+pub const MAX_RETRIES: u8 = 5;
+
+/// A global counter.
+pub static COUNTER: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// A result with a string error.
+pub type Result<T> = std::result::Result<T, String>;
+
+/// An old function.
+#[deprecated(since = "0.1.0", note = "use `new_api` instead")]
+pub fn old_api() {}
+
+/// The new function.
+pub fn new_api() {}
+
+#[doc(hidden)]
+pub fn hidden_fn() {}
+
+/// Reads from a raw pointer.
+pub unsafe fn dangerous(ptr: *const u8) -> u8 {
+    unsafe { *ptr }
+}
+
+pub fn undocumented() {}
+
+pub(crate) fn internal() {}
+
+/// Prints a greeting.
+#[macro_export]
+macro_rules! hello {
+    () => {
+        println!("hello")
+    };
+}
@@ -0,0 +1,74 @@
+//! Version 0.1.0 of a crate for testing API diffs against `versioned_v2.rs`.
+//!
+//! `versioned_v1.json` is generated from this file, regenerate it from the repository root with:
+//!
+//! ```sh
+//! RUSTC_BOOTSTRAP=1 rustdoc -Z unstable-options --output-format json --edition 2024 \
+//!     --crate-name versioned --crate-version 0.1.0 -o target/versioned tests/fixtures/versioned_v1.rs
+//! mv target/versioned/versioned.json tests/fixtures/versioned_v1.json
+//! ```
+
+/// Settings anyone can construct.
+pub struct Settings {
+    /// Print more
+    pub verbose: bool,
+    /// The log level
+    pub level: u8,
+}
+
+/// Options that may gain fields.
+#[non_exhaustive]
+pub struct Options {
+    /// Go fast
+    pub fast: bool,
+}
+
+/// An exhaustive enum.
+pub enum Mode {
+    /// Read only
+    Read,
+    /// Read and write
+    Write,
+}
+
+/// An enum that may gain variants.
+#[non_exhaustive]
+pub enum Event {
+    /// Opened
+    Open,
+    /// Closed
+    Close,
+}
+
+/// A token.
+#[derive(Clone)]
+pub struct Token;
+
+/// A handle that is `Send` and `Sync`.
+pub struct Handle {
+    inner: u8,
+}
+
+impl Handle {
+    /// Returns the inner value.
+    pub fn get(&self) -> u8 {
+        self.inner
+    }
+}
+
+/// Handles events.
+pub trait Handler {
+    /// Handles an event.
+    fn handle(&self, event: Event);
+}
+
+/// Parses the input.
+pub fn parse(input: &str) -> u32 {
+    input.len() as u32
+}
+
+/// A stable function.
+pub fn stable() {}
+
+/// A function that is removed in the next version.
+pub fn removed() {}
@@ -0,0 +1,89 @@
+//! Version 0.2.0 of a crate for testing API diffs against `versioned_v1.rs`.
+//!
+//! `versioned_v2.json` is generated from this file, regenerate it from the repository root with:
+//!
+//! ```sh
+//! RUSTC_BOOTSTRAP=1 rustdoc -Z unstable-options --output-format json --edition 2024 \
+//!     --crate-name versioned --crate-version 0.2.0 -o target/versioned tests/fixtures/versioned_v2.rs
+//! mv target/versioned/versioned.json tests/fixtures/versioned_v2.json
+//! ```
+
+/// Settings anyone can construct.
+pub struct Settings {
+    /// Print more
+    pub verbose: bool,
+    /// The log level
+    pub level: u8,
+    /// Use colors
+    pub color: bool,
+}
+
+/// Options that may gain fields.
+#[non_exhaustive]
+pub struct Options {
+    /// Go fast
+    pub fast: bool,
+    /// Be careful
+    pub safe: bool,
+}
+
+/// An exhaustive enum.
+pub enum Mode {
+    /// Read only
+    Read,
+    /// Read and write
+    Write,
+    /// Append only
+    Append,
+}
+
+/// An enum that may gain variants.
+#[non_exhaustive]
+pub enum Event {
+    /// Opened
+    Open,
+    /// Closed
+    Close,
+    /// Flushed
+    Flush,
+}
+
+/// A token.
+#[derive(Debug)]
+pub struct Token;
+
+/// A handle that is no longer `Send` and `Sync`.
+pub struct Handle {
+    inner: std::rc::Rc<u8>,
+}
+
+impl Handle {
+    /// Returns the inner value.
+    pub fn get(&self) -> u8 {
+        *self.inner
+    }
+}
+
+/// Handles events.
+pub trait Handler {
+    /// Handles an event.
+    fn handle(&self, event: Event);
+
+    /// Flushes, does nothing by default.
+    fn flush(&self) {}
+
+    /// Closes the handler.
+    fn close(&self);
+}
+
+/// Parses the input.
+pub fn parse(input: &str) -> u64 {
+    input.len() as u64
+}
+
+/// A stable function.
+#[deprecated(since = "0.2.0", note = "use `added` instead")]
+pub fn stable() {}
+
+/// A function added in this version.
+pub fn added() {}
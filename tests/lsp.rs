@@ -0,0 +1,223 @@
+//! Drives `docsrs-lsp` over stdio with a scripted client, serving the fixture docs
+
+#![cfg(feature = "lsp")]
+
+use serde_json::{Value, json};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Client {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_docsrs-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Self {
+            child,
+            stdin,
+            stdout,
+            next_id: 0,
+        }
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            self.stdout.read_line(&mut header).unwrap();
+            let header = header.trim();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("Content-Length:") {
+                length = value.trim().parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        self.stdout.read_exact(&mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    /// Sends a request and returns the result of its response
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        loop {
+            let message = self.receive();
+            if message["id"] == id {
+                assert!(message["error"].is_null(), "{method} failed: {message}");
+                return message["result"].clone();
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    fn shutdown(mut self) {
+        self.request("shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+fn start_with_fixture() -> Client {
+    let mut client = Client::start();
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/fixture.json");
+    let result = client.request(
+        "initialize",
+        json!({
+            "processId": null,
+            "rootUri": null,
+            "capabilities": {},
+            "initializationOptions": { "docs": [fixture] },
+        }),
+    );
+    assert_eq!(result["capabilities"]["hoverProvider"], true);
+    assert_eq!(result["capabilities"]["workspaceSymbolProvider"], true);
+    client.notify("initialized", json!({}));
+    client
+}
+
+#[test]
+fn workspace_symbol() {
+    let mut client = start_with_fixture();
+
+    let symbols = client.request("workspace/symbol", json!({ "query": "Client" }));
+    let client_struct = symbols
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|symbol| symbol["name"] == "Client" && symbol["kind"] == 23)
+        .expect("the Client struct is found");
+    assert!(
+        client_struct["location"]["uri"]
+            .as_str()
+            .unwrap()
+            .starts_with("https://docs.rs/fixture/0.1.0/")
+    );
+
+    // Fields have no page of their own and link to the page of their struct
+    let symbols = client.request("workspace/symbol", json!({ "query": "retries" }));
+    let field = symbols
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|symbol| symbol["name"] == "retries")
+        .expect("the retries field is found");
+    assert_eq!(field["containerName"], "fixture::config::Config");
+    assert_eq!(
+        field["location"]["uri"],
+        "https://docs.rs/fixture/0.1.0/config/struct.Config.html"
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn custom_search() {
+    let mut client = start_with_fixture();
+
+    let results = client.request(
+        "docsrs/search",
+        json!({ "query": "Buffer::len", "limit": 1 }),
+    );
+    let results = results.as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["path"], "fixture::Buffer::len");
+    assert_eq!(results[0]["kind"], "function");
+    assert_eq!(results[0]["signature"], "fn len(&self) -> usize");
+
+    client.shutdown();
+}
+
+#[test]
+fn hover() {
+    let mut client = start_with_fixture();
+
+    let uri = "file:///project/src/main.rs";
+    let text = "fn main() {\n    let buffer = fixture::Buffer::default();\n    something_unknown();\n    \
+                fixture::shapes::is_empty();\n    fixture::shapes::Shape::is_empty(&shape);\n    \
+                Wrapper::into_inner(wrapper);\n}\n";
+    client.notify(
+        "textDocument/didOpen",
+        json!({
+            "textDocument": { "uri": uri, "languageId": "rust", "version": 1, "text": text },
+        }),
+    );
+
+    let hover = client.request(
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": uri }, "position": { "line": 1, "character": 27 } }),
+    );
+    let contents = hover["contents"]["value"].as_str().unwrap();
+    assert!(contents.contains("struct Buffer"), "{contents}");
+    assert!(contents.contains("A growable byte buffer."), "{contents}");
+    assert_eq!(
+        hover["range"]["start"],
+        json!({ "line": 1, "character": 17 })
+    );
+    assert_eq!(hover["range"]["end"], json!({ "line": 1, "character": 32 }));
+
+    let hover = client.request(
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": uri }, "position": { "line": 2, "character": 8 } }),
+    );
+    assert!(hover.is_null());
+
+    // `is_empty` of another type isn't shown for a path that doesn't exist
+    let hover = client.request(
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": uri }, "position": { "line": 3, "character": 22 } }),
+    );
+    assert!(hover.is_null(), "{hover}");
+
+    let hover = client.request(
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": uri }, "position": { "line": 4, "character": 30 } }),
+    );
+    let contents = hover["contents"]["value"].as_str().unwrap();
+    assert!(
+        contents.contains("Returns whether the shape is empty."),
+        "{contents}"
+    );
+
+    // Paths relative to an import match the end of an item's path
+    let hover = client.request(
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": uri }, "position": { "line": 5, "character": 6 } }),
+    );
+    let contents = hover["contents"]["value"].as_str().unwrap();
+    assert!(contents.contains("struct Wrapper"), "{contents}");
+
+    let hover = client.request(
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": uri }, "position": { "line": 5, "character": 16 } }),
+    );
+    let contents = hover["contents"]["value"].as_str().unwrap();
+    assert!(
+        contents.contains("fn into_inner(self) -> Buffer"),
+        "{contents}"
+    );
+    assert!(contents.contains("Consumes the wrapper."), "{contents}");
+
+    client.shutdown();
+}
@@ -0,0 +1,225 @@
+//! Drives `docsrs mcp` over stdio with a scripted client, serving the fixture docs
+
+#![cfg(feature = "fetch")]
+
+use serde_json::{Value, json};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Client {
+    fn start() -> Self {
+        let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/fixture.json");
+        let mut child = Command::new(env!("CARGO_BIN_EXE_docsrs"))
+            .args(["mcp", "--docs", fixture])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Self {
+            child,
+            stdin,
+            stdout,
+            next_id: 0,
+        }
+    }
+
+    fn send_line(&mut self, line: &str) {
+        writeln!(self.stdin, "{line}").unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut line = String::new();
+        self.stdout.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    /// Sends a request and returns its response
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        self.send_line(&request.to_string());
+        let response = self.receive();
+        assert_eq!(response["id"], id, "responses come in order");
+        response
+    }
+
+    /// Calls a tool and returns the result of the response
+    fn call(&mut self, tool: &str, arguments: Value) -> Value {
+        let response = self.request(
+            "tools/call",
+            json!({ "name": tool, "arguments": arguments }),
+        );
+        assert!(response["error"].is_null(), "{tool} failed: {response}");
+        response["result"].clone()
+    }
+
+    fn shutdown(mut self) {
+        drop(self.stdin);
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+fn start_initialized() -> Client {
+    let mut client = Client::start();
+    let response = client.request(
+        "initialize",
+        json!({
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": { "name": "test", "version": "0" },
+        }),
+    );
+    assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+    assert_eq!(response["result"]["serverInfo"]["name"], "docsrs");
+    client
+        .send_line(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }).to_string());
+    client
+}
+
+#[test]
+fn framing() {
+    let mut client = start_initialized();
+
+    // Blank lines and notifications get no response, so the next response is the ping's
+    client.send_line("");
+    client.send_line("{not json");
+    let parse_error = client.receive();
+    assert_eq!(parse_error["id"], Value::Null);
+    assert_eq!(parse_error["error"]["code"], -32700);
+    assert_eq!(client.request("ping", Value::Null)["result"], json!({}));
+
+    let unknown = client.request("resources/list", json!({}));
+    assert_eq!(unknown["error"]["code"], -32601);
+
+    // An unknown protocol version is answered with the latest one
+    let response = client.request("initialize", json!({ "protocolVersion": "1999-01-01" }));
+    assert_eq!(response["result"]["protocolVersion"], "2025-06-18");
+
+    client.shutdown();
+}
+
+#[test]
+fn list_tools() {
+    let mut client = start_initialized();
+
+    let response = client.request("tools/list", json!({}));
+    let tools = response["result"]["tools"].as_array().unwrap();
+    let names: Vec<&str> = tools
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect();
+    assert_eq!(
+        names,
+        ["search_docs", "get_item", "list_module", "list_methods"]
+    );
+    for tool in tools {
+        assert_eq!(tool["inputSchema"]["type"], "object");
+        assert!(
+            tool["inputSchema"]["required"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("crate"))
+        );
+        assert_eq!(tool["outputSchema"]["type"], "object");
+    }
+
+    client.shutdown();
+}
+
+#[test]
+fn validate_arguments() {
+    let mut client = start_initialized();
+
+    let invalid = [
+        ("search_docs", json!({ "query": "Client" })),
+        ("search_docs", json!({ "crate": "fixture" })),
+        (
+            "search_docs",
+            json!({ "crate": "fixture", "query": "x", "kind": "class" }),
+        ),
+        ("get_item", json!({ "crate": "fixture", "path": 1 })),
+        ("no_such_tool", json!({ "crate": "fixture" })),
+    ];
+    for (tool, arguments) in invalid {
+        let response = client.request(
+            "tools/call",
+            json!({ "name": tool, "arguments": arguments }),
+        );
+        assert_eq!(response["error"]["code"], -32602, "{tool} {arguments}");
+    }
+
+    // Failures of valid calls are tool results for the model to read
+    let result = client.call(
+        "get_item",
+        json!({ "crate": "fixture", "path": "fixture::Missing" }),
+    );
+    assert_eq!(result["isError"], true);
+    assert_eq!(
+        result["content"][0]["text"],
+        "No item `fixture::Missing` found in crate `fixture`"
+    );
+
+    client.shutdown();
+}
+
+#[test]
+fn call_tools() {
+    let mut client = start_initialized();
+
+    let result = client.call(
+        "search_docs",
+        json!({ "crate": "fixture", "query": "Client", "kind": "struct", "limit": 1 }),
+    );
+    assert_eq!(result["isError"], false);
+    let results = result["structuredContent"]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["path"], "fixture::client::Client");
+    let text: Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(text, result["structuredContent"]);
+
+    let item = &client.call(
+        "get_item",
+        json!({ "crate": "fixture", "path": "fixture::config::Config::new" }),
+    )["structuredContent"]["item"];
+    assert_eq!(item["signature"], "fn new() -> Self");
+    assert_eq!(item["docs"], "Creates a config with the default timeout.");
+
+    let items = client.call(
+        "list_module",
+        json!({ "crate": "fixture", "path": "fixture::shapes" }),
+    )["structuredContent"]["items"]
+        .clone();
+    assert!(items.as_array().unwrap().contains(&json!({
+        "name": "Area",
+        "kind": "trait",
+        "summary": "Something with an area.",
+    })));
+
+    let result = client.call(
+        "list_methods",
+        json!({ "crate": "fixture", "type": "fixture::Wrapper" }),
+    )["structuredContent"]
+        .clone();
+    assert_eq!(result["type"], "fixture::Wrapper");
+    let len = result["methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|method| method["name"] == "len")
+        .expect("`len` is reachable through `Deref`");
+    assert_eq!(len["origin"], "deref");
+    assert_eq!(len["via"], "fixture::Buffer");
+
+    client.shutdown();
+}